@@ -1,15 +1,19 @@
+#[cfg(not(feature = "std"))]
+use crate::compat::Vec;
 use crate::{
     automaton::Automaton::*,
     dfa::DFA,
     nfa::{ToNfa, NFA},
     regex::Regex,
 };
-use std::{
+use core::{
     cmp::{Ordering, Ordering::*, PartialEq, PartialOrd},
-    fmt::{Debug, Display},
+    fmt::{self, Debug, Display, Formatter},
     hash::Hash,
     ops::RangeBounds,
 };
+#[cfg(feature = "std")]
+use std::error::Error;
 
 ///
 /// Automaton<V> regroups [`NFA<V>`], [`DFA<V>`] and [`Regex<V>`] where `V` is the type of the [`alphabet`].
@@ -21,7 +25,7 @@ use std::{
 ///
 
 #[derive(Debug)]
-pub enum Automaton<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> {
+pub enum Automaton<V: Eq + Hash + Display + Clone + Debug + Ord> {
     /// This variant represents a [`DFA`](dfa/struct.DFA.html).
     DFA(DFA<V>),
     /// This variant represents a [`NFA`](dfa/struct.NFA.html).
@@ -34,7 +38,7 @@ pub enum Automaton<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> {
 /// An interface to regroup functions used to build Automata.
 ///
 
-pub trait Buildable<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> {
+pub trait Buildable<V: Eq + Hash + Display + Clone + Debug + Ord> {
     /// Returns the automaton that accepts a word if and only if it is accepted by `self` or by `other`.
     fn unite(self, other: Self) -> Self;
     /// Returns the automaton that accepts a word if and only if it is the concatenation of a word accepted by `self` and of a word accepted by `other`.
@@ -47,6 +51,20 @@ pub trait Buildable<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> {
     fn at_least(self, num: usize) -> Self;
     /// Returns the automaton that accepts a word if and only if it is the concatenation of a number in the range `r` of words accepted by `self`.
     fn repeat<R: RangeBounds<usize>>(self, r: R) -> Self;
+    /// Returns the automaton that accepts a word if and only if it is the concatenation of at least one word accepted by `self`, like the `+` of regexes.
+    fn kleene_plus(self) -> Self
+    where
+        Self: Sized,
+    {
+        self.at_least(1)
+    }
+    /// Returns the automaton that accepts a word if and only if it is empty or accepted by `self`, like the `?` of regexes.
+    fn optional(self) -> Self
+    where
+        Self: Sized,
+    {
+        self.repeat(0..=1)
+    }
 }
 
 ///
@@ -74,9 +92,16 @@ pub trait Buildable<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> {
 /// An automaton is said `full` if it accepts any `word`.
 ///
 
-pub trait Automata<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> {
+pub trait Automata<V: Eq + Hash + Display + Clone + Debug + Ord> {
     /// Returns `true` if and only if `word` is accepted by `self`.
     fn run(&self, word: &[V]) -> bool;
+    /// Returns `true` if and only if the word made of the letters of `word` is accepted by `self`.
+    fn run_iter<I: IntoIterator<Item = V>>(&self, word: I) -> bool
+    where
+        Self: Sized,
+    {
+        self.run(&word.into_iter().collect::<Vec<_>>())
+    }
 
     /// Returns `true` if and only if `self` is [`complete`](./trait.Automata.html#complete-automaton).
     fn is_complete(&self) -> bool;
@@ -106,14 +131,38 @@ pub trait Automata<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> {
 }
 
 #[derive(Debug)]
-pub enum FromRawError<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> {
+pub enum FromRawError<V: Eq + Hash + Display + Clone + Debug + Ord> {
     UnknownLetter(V),
     InvalidInitial(usize),
     InvalidFinal(usize),
     InvalidTransition(usize, V, usize),
+    InvalidEpsilonTransition(usize, usize),
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automaton<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Display for FromRawError<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            FromRawError::UnknownLetter(v) => write!(f, "Letter {} is not in the alphabet", v),
+            FromRawError::InvalidInitial(s) => write!(f, "Initial state {} doesn't exist", s),
+            FromRawError::InvalidFinal(s) => write!(f, "Final state {} doesn't exist", s),
+            FromRawError::InvalidTransition(s, v, t) => write!(
+                f,
+                "Transition from {} to {} labeled by {} leads to a state that doesn't exist",
+                s, t, v
+            ),
+            FromRawError::InvalidEpsilonTransition(s, t) => write!(
+                f,
+                "Epsilon transition from {} to {} involves a state that doesn't exist",
+                s, t
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Error for FromRawError<V> {}
+
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Automaton<V> {
     /// A contains B if and only if for each `word` w, if B `accepts` w then A `accepts` w.
     pub fn contains(&self, other: &Automaton<V>) -> bool {
         let a = match self {
@@ -131,25 +180,25 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automaton<V> {
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<Automaton<V>> for Automaton<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<Automaton<V>> for Automaton<V> {
     fn eq(&self, other: &Automaton<V>) -> bool {
         self.le(other) && self.ge(other)
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<DFA<V>> for Automaton<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<DFA<V>> for Automaton<V> {
     fn eq(&self, other: &DFA<V>) -> bool {
         self.eq(&other.to_nfa())
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<Regex<V>> for Automaton<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<Regex<V>> for Automaton<V> {
     fn eq(&self, other: &Regex<V>) -> bool {
         self.eq(&other.to_nfa())
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<NFA<V>> for Automaton<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<NFA<V>> for Automaton<V> {
     fn eq(&self, other: &NFA<V>) -> bool {
         match self {
             Automaton::DFA(v) => other.eq(&*v),
@@ -162,7 +211,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<NFA<V>> for
 /// The partial ordering on two automatons A and B is defined as A < B if and only if B [`contains`] A.
 ///
 /// [`contains`]: ./enum.Automaton.html#method.contains
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialOrd for Automaton<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialOrd for Automaton<V> {
     fn partial_cmp(&self, other: &Automaton<V>) -> Option<Ordering> {
         match (self.ge(&other), self.le(&other)) {
             (true, true) => Some(Equal),
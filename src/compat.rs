@@ -0,0 +1,16 @@
+//! The collections and `alloc` items used throughout the crate, taken from `std` or, without the `std` feature, from `alloc` and `hashbrown`.
+
+#[cfg(feature = "std")]
+pub(crate) use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
+
+#[cfg(not(feature = "std"))]
+pub(crate) use alloc::{
+    boxed::Box,
+    collections::{BTreeSet, VecDeque},
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+#[cfg(not(feature = "std"))]
+pub(crate) use hashbrown::{HashMap, HashSet};
@@ -1,39 +1,353 @@
 use crate::{
     automaton::{Automata, Automaton, Buildable, FromRawError},
+    compat::*,
     nfa::{ToNfa, NFA},
-    regex::{Regex, ToRegex},
+    regex::{ParseError, Regex, ToRegex},
+    utils::*,
 };
-use std::{
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+use core::{
+    borrow::Borrow,
     cmp::{Ordering, Ordering::*, PartialEq, PartialOrd},
-    collections::{HashMap, HashSet},
-    fmt::{Debug, Display},
-    hash::Hash,
-    ops::{Add, Mul, Neg, Not, RangeBounds, Sub},
+    fmt::{self, Debug, Display, Formatter},
+    hash::{Hash, Hasher},
+    iter::from_fn,
+    ops::{Add, BitAnd, BitOr, Mul, Neg, Not, RangeBounds, Sub},
     str::FromStr,
 };
+#[cfg(feature = "rand")]
+use rand::Rng;
+#[cfg(feature = "json")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// <https://en.wikipedia.org/wiki/Deterministic_finite_automaton>
 #[derive(Debug, Clone)]
-pub struct DFA<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawDFA<V>"))]
+pub struct DFA<V: Eq + Hash + Display + Clone + Debug + Ord> {
     pub(crate) alphabet: HashSet<V>,
     pub(crate) initial: usize,
     pub(crate) finals: HashSet<usize>,
     pub(crate) transitions: Vec<HashMap<V, usize>>,
 }
 
+/// The unchecked fields of a deserialized DFA, validated by [`DFA::from_raw`](struct.DFA.html#method.from_raw).
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawDFA<V: Eq + Hash + Display + Clone + Debug + Ord> {
+    alphabet: HashSet<V>,
+    initial: usize,
+    finals: HashSet<usize>,
+    transitions: Vec<HashMap<V, usize>>,
+}
+
+#[cfg(feature = "serde")]
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> TryFrom<RawDFA<V>> for DFA<V> {
+    type Error = FromRawError<V>;
+
+    fn try_from(raw: RawDFA<V>) -> Result<DFA<V>, FromRawError<V>> {
+        DFA::from_raw(raw.alphabet, raw.initial, raw.finals, raw.transitions)
+    }
+}
+
+/// An incremental matcher over a [`DFA`](struct.DFA.html), created by [`DFA::runner`](struct.DFA.html#method.runner).
+#[derive(Debug, Clone)]
+pub struct DfaRunner<'a, V: Eq + Hash + Display + Clone + Debug + Ord> {
+    dfa: &'a DFA<V>,
+    alive: HashSet<usize>,
+    state: Option<usize>,
+}
+
 /// An interface for structs that can be converted into a DFA.
-pub trait ToDfa<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> {
+pub trait ToDfa<V: Eq + Hash + Display + Clone + Debug + Ord> {
     fn to_dfa(&self) -> DFA<V>;
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> DFA<V> {
     pub fn intersect(self, b: DFA<V>) -> DFA<V> {
         self.negate().unite(b.negate()).negate()
     }
 
+    /// Returns a DFA that accepts a word if and only if this word is accepted by exactly one of `self` and `b`.
+    pub fn symmetric_difference(self, b: DFA<V>) -> DFA<V> {
+        self.to_nfa().symmetric_difference(b.to_nfa()).to_dfa()
+    }
+
+    /// Returns a DFA that accepts a word if and only if `self` doesn't accept this word, by completing `self` and swapping its final and non-final states.
+    pub fn complement(mut self) -> DFA<V> {
+        self = self.complete();
+        self.finals = (0..self.transitions.len())
+            .filter(|x| !self.finals.contains(&x))
+            .collect();
+        self
+    }
+
+    /// Returns a DFA that accepts a word over the union of `alphabet` and of the alphabet of `self` if and only if `self` doesn't accept this word.
+    pub fn complement_over(mut self, alphabet: &HashSet<V>) -> DFA<V> {
+        self.alphabet.extend(alphabet.iter().cloned());
+        self.negate()
+    }
+
     /// The algorithm used is <https://en.wikipedia.org/wiki/DFA_minimization#Brzozowski's_algorithm>.
+    /// The unreachable states are removed first so that they don't enlarge the reversed automatons.
+    /// The returned automaton is the minimal partial automaton of the language of `self`, it has no dead state and isn't complete in general,
+    /// so it doesn't pass [`is_minimal`](#method.is_minimal) unlike the result of [`minimize_hopcroft`](#method.minimize_hopcroft).
     pub fn minimize(self) -> DFA<V> {
-        self.reverse().to_dfa().reverse().to_dfa()
+        self.to_nfa()
+            .make_reachable()
+            .reverse()
+            .to_dfa()
+            .reverse()
+            .to_dfa()
+    }
+
+    /// Returns a [pumping length](https://en.wikipedia.org/wiki/Pumping_lemma_for_regular_languages) of the language of `self`, the number of states of its minimal partial automaton.
+    /// Any word accepted by `self` whose length is at least this constant can be written `xyz` with `y` non-empty and `|xy|` at most this constant, such that `self` accepts `xy^iz` for every `i`.
+    pub fn pumping_length(&self) -> usize {
+        self.clone().minimize().transitions.len()
+    }
+
+    /// Returns the number of non-empty [residual languages](https://en.wikipedia.org/wiki/Myhill%E2%80%93Nerode_theorem) of the language of `self`, the sets of words `w` such that `self` accepts `uw`, for each word `u`.
+    /// It is the number of states of its minimal partial automaton, returned by [`minimize`](#method.minimize), minus its dead state, if any.
+    pub fn residual_count(&self) -> usize {
+        let dfa = self.clone().minimize();
+        dfa.transitions.len() - dfa.dead_states().len()
+    }
+
+    /// The algorithm used is <https://en.wikipedia.org/wiki/DFA_minimization#Hopcroft's_algorithm>.
+    /// The returned automaton is complete.
+    pub fn minimize_hopcroft(self) -> DFA<V> {
+        self.make_reachable().minimize_with_map().0
+    }
+
+    /// Returns the automaton whose states are the classes of indistinguishable states of `self`, computed with <https://en.wikipedia.org/wiki/DFA_minimization#Hopcroft's_algorithm>, and the class of each state of `self`.
+    /// The returned automaton is complete, and minimal if `self` is [`reachable`](../automaton/trait.Automata.html#reachable-automaton).
+    pub fn minimize_with_map(self) -> (DFA<V>, Vec<usize>) {
+        let l = self.transitions.len();
+        let dfa = self.complete();
+        let (blocks, mut block_of) = dfa.refine_partition();
+
+        let minimized = DFA {
+            initial: block_of[dfa.initial],
+            finals: dfa.finals.iter().map(|x| block_of[*x]).collect(),
+            transitions: blocks
+                .iter()
+                .map(|block| {
+                    dfa.transitions[block[0]]
+                        .iter()
+                        .map(|(v, t)| (v.clone(), block_of[*t]))
+                        .collect()
+                })
+                .collect(),
+            alphabet: dfa.alphabet,
+        };
+
+        // the state added by `complete` isn't a state of `self`
+        block_of.truncate(l);
+        (minimized, block_of)
+    }
+
+    /// Returns the partition of the states of `self` into [Myhill–Nerode](https://en.wikipedia.org/wiki/Myhill%E2%80%93Nerode_theorem) classes, the states that no suffix distinguishes.
+    pub fn equivalence_classes(&self) -> Vec<HashSet<usize>> {
+        let l = self.transitions.len();
+        let (blocks, _) = self.clone().complete().refine_partition();
+        blocks
+            .into_iter()
+            .map(|block| block.into_iter().filter(|x| *x < l).collect::<HashSet<_>>())
+            .filter(|block| !block.is_empty())
+            .collect()
+    }
+
+    /// Returns `true` if and only if `self` is the minimal complete automaton of its language: it is complete, reachable, and no two of its states are equivalent.
+    /// In particular, it has at most one dead state.
+    /// The result of [`minimize_hopcroft`](#method.minimize_hopcroft) passes this check, but not the partial result of [`minimize`](#method.minimize) in general.
+    pub fn is_minimal(&self) -> bool {
+        self.is_complete()
+            && self.is_reachable()
+            && self.equivalence_classes().len() == self.transitions.len()
+    }
+
+    /// Returns the blocks of indistinguishable states of `self`, which must be complete, and the block of each state.
+    fn refine_partition(&self) -> (Vec<Vec<usize>>, Vec<usize>) {
+        let l = self.transitions.len();
+        let mut alphabet: Vec<V> = self.alphabet.iter().cloned().collect();
+        alphabet.sort();
+
+        let mut inverse: HashMap<V, Vec<Vec<usize>>> = alphabet
+            .iter()
+            .map(|v| (v.clone(), vec![Vec::new(); l]))
+            .collect();
+        for (i, map) in self.transitions.iter().enumerate() {
+            for (v, t) in map {
+                inverse.get_mut(v).unwrap()[*t].push(i);
+            }
+        }
+
+        let (finals, others): (Vec<usize>, Vec<usize>) =
+            (0..l).partition(|x| self.finals.contains(x));
+        let mut blocks: Vec<Vec<usize>> = vec![finals, others]
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .collect();
+        let mut block_of = vec![0; l];
+        for (i, block) in blocks.iter().enumerate() {
+            for e in block {
+                block_of[*e] = i;
+            }
+        }
+
+        let mut waiting: HashSet<(usize, V)> = HashSet::new();
+        let mut queue = VecDeque::new();
+        for i in 0..blocks.len() {
+            for v in &alphabet {
+                waiting.insert((i, v.clone()));
+                queue.push_back((i, v.clone()));
+            }
+        }
+
+        while let Some((a, v)) = queue.pop_front() {
+            waiting.remove(&(a, v.clone()));
+
+            // the states that lead to the block `a` with the letter `v`, grouped by block
+            let mut touched: HashMap<usize, HashSet<usize>> = HashMap::new();
+            for e in &blocks[a] {
+                for x in &inverse[&v][*e] {
+                    touched.entry(block_of[*x]).or_default().insert(*x);
+                }
+            }
+
+            for (y, set) in touched {
+                if set.len() == blocks[y].len() {
+                    continue;
+                }
+
+                let (inside, outside): (Vec<usize>, Vec<usize>) =
+                    blocks[y].iter().partition(|x| set.contains(x));
+                let new = blocks.len();
+                for e in &outside {
+                    block_of[*e] = new;
+                }
+                let smaller = if inside.len() <= outside.len() {
+                    y
+                } else {
+                    new
+                };
+                blocks[y] = inside;
+                blocks.push(outside);
+
+                for c in &alphabet {
+                    if waiting.contains(&(y, c.clone())) {
+                        waiting.insert((new, c.clone()));
+                        queue.push_back((new, c.clone()));
+                    } else {
+                        waiting.insert((smaller, c.clone()));
+                        queue.push_back((smaller, c.clone()));
+                    }
+                }
+            }
+        }
+
+        (blocks, block_of)
+    }
+
+    /// Returns the minimal automaton of `self` where the states are numbered in the order of a breadth-first search from the initial state (the letters being visited in increasing order).
+    /// Two automata accept the same words if and only if their canonical forms have the same states and transitions.
+    pub fn canonical(self) -> DFA<V> {
+        let dfa = self.minimize();
+        let mut alphabet: Vec<V> = dfa.alphabet.iter().cloned().collect();
+        alphabet.sort();
+
+        let mut map = HashMap::new();
+        let mut queue = VecDeque::new();
+        map.insert(dfa.initial, 0);
+        queue.push_back(dfa.initial);
+        let mut order = Vec::new();
+
+        while let Some(e) = queue.pop_front() {
+            order.push(e);
+            for v in &alphabet {
+                if let Some(t) = dfa.transitions[e].get(v) {
+                    if !map.contains_key(t) {
+                        map.insert(*t, map.len());
+                        queue.push_back(*t);
+                    }
+                }
+            }
+        }
+
+        DFA {
+            initial: 0,
+            finals: dfa
+                .finals
+                .iter()
+                .filter_map(|x| map.get(x))
+                .cloned()
+                .collect(),
+            transitions: order
+                .into_iter()
+                .map(|e| {
+                    dfa.transitions[e]
+                        .iter()
+                        .map(|(v, t)| (v.clone(), *map.get(t).unwrap()))
+                        .collect()
+                })
+                .collect(),
+            alphabet: dfa.alphabet,
+        }
+    }
+
+    /// Returns `true` if and only if `self` and `other` accept the same words, by comparing their [`canonical`](#method.canonical) forms.
+    pub fn is_equivalent(&self, other: &DFA<V>) -> bool {
+        let a = self.clone().canonical();
+        let b = other.clone().canonical();
+        a.finals == b.finals && a.transitions == b.transitions
+    }
+
+    /// Returns `true` if and only if `self` and `other` are the same automaton up to a renumbering of the states.
+    /// The states are matched by a breadth-first search from the initial states, so `false` is returned if one of the automata has unreachable states.
+    pub fn is_isomorphic(&self, other: &DFA<V>) -> bool {
+        let l = self.transitions.len();
+        if l != other.transitions.len() {
+            return false;
+        }
+
+        let mut map = vec![None; l];
+        let mut inverse = vec![None; l];
+        map[self.initial] = Some(other.initial);
+        inverse[other.initial] = Some(self.initial);
+        let mut queue = VecDeque::new();
+        queue.push_back((self.initial, other.initial));
+        let mut count = 1;
+
+        while let Some((i, j)) = queue.pop_front() {
+            if self.finals.contains(&i) != other.finals.contains(&j)
+                || self.transitions[i].len() != other.transitions[j].len()
+            {
+                return false;
+            }
+
+            for (v, x) in &self.transitions[i] {
+                let y = match other.transitions[j].get(v) {
+                    Some(y) => *y,
+                    None => return false,
+                };
+                match (map[*x], inverse[y]) {
+                    (None, None) => {
+                        map[*x] = Some(y);
+                        inverse[y] = Some(*x);
+                        queue.push_back((*x, y));
+                        count += 1;
+                    }
+                    (Some(a), Some(b)) if a == y && b == *x => {}
+                    _ => return false,
+                }
+            }
+        }
+
+        count == l
     }
 
     /// A contains B if and only if for each `word` w, if B `accepts` w then A `accepts` w.
@@ -41,9 +355,467 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> DFA<V> {
         self.to_nfa().contains(&b.to_nfa())
     }
 
-    /// Returns a string containing the dot description of the automaton
+    /// Returns a DFA that accepts a word w if and only if there is a word u accepted by `other` such that `self` accepts the concatenation of w and u.
+    pub fn right_quotient(&self, other: &NFA<V>) -> DFA<V> {
+        let other = other.clone().remove_epsilon();
+        let reversed = self.to_nfa().reverse();
+        let other_reversed = other.clone().reverse();
+
+        // backward reachability in the product of `self` and `other`, from the pairs of final states
+        let mut acc = HashSet::new();
+        let mut stack = Vec::new();
+        for &i in &self.finals {
+            for &j in &other.finals {
+                acc.insert((i, j));
+                stack.push((i, j));
+            }
+        }
+
+        while let Some((i, j)) = stack.pop() {
+            for (v, ti) in &reversed.transitions[i] {
+                if let Some(tj) = other_reversed.transitions[j].get(v) {
+                    for &x in ti {
+                        for &y in tj {
+                            if acc.insert((x, y)) {
+                                stack.push((x, y));
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut dfa = self.clone();
+        dfa.finals = acc
+            .into_iter()
+            .filter(|(_, j)| other.initials.contains(j))
+            .map(|(i, _)| i)
+            .collect();
+        dfa
+    }
+
+    /// Runs `self` on the letters of `word`, which may be borrowed, so that [`run`](#method.run) doesn't clone them.
+    fn run_letters<B: Borrow<V>, I: IntoIterator<Item = B>>(&self, word: I) -> bool {
+        let mut actual = self.initial;
+        for l in word {
+            let l: &V = l.borrow();
+            if let Some(t) = self.transitions[actual].get(l) {
+                actual = *t;
+            } else {
+                return false;
+            }
+        }
+        self.finals.contains(&actual)
+    }
+
+    /// Returns the states from which a final state can be reached, the others are dead.
+    fn alive_states(&self) -> HashSet<usize> {
+        let mut reverse = vec![Vec::new(); self.transitions.len()];
+        for (i, map) in self.transitions.iter().enumerate() {
+            for e in map.values() {
+                reverse[*e].push(i);
+            }
+        }
+
+        let mut alive: HashSet<usize> = self.finals.clone();
+        let mut stack: Vec<usize> = self.finals.iter().cloned().collect();
+        while let Some(e) = stack.pop() {
+            for x in &reverse[e] {
+                if alive.insert(*x) {
+                    stack.push(*x);
+                }
+            }
+        }
+
+        alive
+    }
+
+    /// Returns `true` if and only if `self` accepts every word over `alphabet`, which may differ from the alphabet of `self`.
+    /// The letters of `alphabet` that aren't in the alphabet of `self` lead nowhere, so `self` isn't universal over `alphabet` as soon as such a letter exists.
+    pub fn is_universal_over(&self, alphabet: &HashSet<V>) -> bool {
+        let mut acc = HashSet::new();
+        let mut stack = vec![self.initial];
+        acc.insert(self.initial);
+
+        while let Some(e) = stack.pop() {
+            if !self.finals.contains(&e) {
+                return false;
+            }
+            for v in alphabet {
+                match self.transitions[e].get(v) {
+                    Some(&t) => {
+                        if acc.insert(t) {
+                            stack.push(t);
+                        }
+                    }
+                    None => return false,
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Returns the dead states of `self`, from which no final state can be reached.
+    pub fn dead_states(&self) -> HashSet<usize> {
+        let alive = self.alive_states();
+        (0..self.transitions.len())
+            .filter(|x| !alive.contains(x))
+            .collect()
+    }
+
+    /// Returns an automaton that accepts the same words as `self` but without its dead states, nor the transitions leading to them.
+    /// The initial state is kept even if it is dead, so the result may be partial: this undoes [`complete`](../automaton/trait.Automata.html#tymethod.complete).
+    pub fn remove_dead(self) -> DFA<V> {
+        let alive = self.alive_states();
+        let kept = |x: &usize| *x == self.initial || alive.contains(x);
+
+        let mut map = vec![None; self.transitions.len()];
+        let mut i = 0;
+        for (state, m) in map.iter_mut().enumerate() {
+            if kept(&state) {
+                *m = Some(i);
+                i += 1;
+            }
+        }
+
+        let transitions = self
+            .transitions
+            .iter()
+            .enumerate()
+            .filter(|(state, _)| kept(state))
+            .map(|(_, tr)| {
+                tr.iter()
+                    .filter_map(|(v, t)| {
+                        if alive.contains(t) {
+                            map[*t].map(|t| (v.clone(), t))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+
+        DFA {
+            initial: map[self.initial].unwrap(),
+            finals: self.finals.iter().filter_map(|x| map[*x]).collect(),
+            transitions,
+            alphabet: self.alphabet,
+        }
+    }
+
+    /// Returns a matcher reading `self` one letter at a time.
+    pub fn runner(&self) -> DfaRunner<'_, V> {
+        DfaRunner {
+            dfa: self,
+            alive: self.alive_states(),
+            state: Some(self.initial),
+        }
+    }
+
+    /// Returns a string containing the dot description of the automaton, with a single start arrow, one edge per pair of states and dead states dashed.
     pub fn to_dot(&self) -> String {
-        self.to_nfa().to_dot()
+        let alive = self.alive_states();
+        let mut finals: Vec<_> = self.finals.iter().collect();
+        finals.sort();
+        let dead: Vec<_> = (0..self.transitions.len())
+            .filter(|x| !alive.contains(x))
+            .collect();
+
+        let mut ret = String::new();
+        ret.push_str("digraph {");
+
+        if !finals.is_empty() {
+            ret.push_str("    node [shape = doublecircle];");
+            for e in finals {
+                ret.push_str(&format!(" S_{}", e));
+            }
+            ret.push(';');
+        }
+
+        ret.push_str(&format!("    node [shape = point]; I_{};", self.initial));
+
+        if !dead.is_empty() {
+            ret.push_str("    node [shape = circle, style = dashed];");
+            for e in dead {
+                ret.push_str(&format!(" S_{}", e));
+            }
+            ret.push(';');
+        }
+
+        ret.push_str("    node [shape = circle, style = solid];");
+        for (i, map) in self.transitions.iter().enumerate() {
+            if map.is_empty() {
+                ret.push_str(&format!("    S_{};", i));
+            }
+            let mut edges = HashMap::new();
+            for (k, v) in map {
+                edges.entry(*v).or_insert_with(Vec::new).push(k.clone());
+            }
+            let mut edges: Vec<_> = edges.into_iter().collect();
+            edges.sort();
+            for (e, mut v) in edges {
+                v.sort();
+                let vs: Vec<_> = v.into_iter().map(|x| x.to_string()).collect();
+                ret.push_str(&format!(
+                    "    S_{} -> S_{} [label = \"{}\"];",
+                    i,
+                    e,
+                    vs.join(", ")
+                ));
+            }
+        }
+
+        ret.push_str(&format!("    I_{} -> S_{};", self.initial, self.initial));
+        ret.push('}');
+
+        ret
+    }
+
+    /// Returns the transition table of the automaton as CSV, with a column per letter and the initial and final states marked by `->` and `*`.
+    pub fn to_csv(&self) -> String {
+        let mut alphabet: Vec<_> = self.alphabet.iter().collect();
+        alphabet.sort();
+
+        let mut ret = String::from("state");
+        for v in &alphabet {
+            ret.push(',');
+            ret.push_str(&csv_field(&v.to_string()));
+        }
+        ret.push('\n');
+
+        for (i, map) in self.transitions.iter().enumerate() {
+            if i == self.initial {
+                ret.push_str("->");
+            }
+            if self.finals.contains(&i) {
+                ret.push('*');
+            }
+            ret.push_str(&i.to_string());
+            for v in &alphabet {
+                ret.push(',');
+                if let Some(t) = map.get(v) {
+                    ret.push_str(&t.to_string());
+                }
+            }
+            ret.push('\n');
+        }
+
+        ret
+    }
+
+    /// Returns the shortest word accepted by `self`, or `None` if `self` is [`empty`](../automaton/trait.Automata.html#empty-automaton).
+    pub fn shortest_accepted(&self) -> Option<Vec<V>> {
+        self.to_nfa().shortest_accepted()
+    }
+
+    /// Returns `true` if and only if `self` accepts a finite number of words.
+    pub fn is_finite(&self) -> bool {
+        self.to_nfa().is_finite()
+    }
+
+    /// Returns the number of words of length `len` accepted by `self`.
+    /// The count saturates at `u128::MAX` instead of overflowing.
+    pub fn count_words_of_length(&self, len: usize) -> u128 {
+        let mut counts = vec![0u128; self.transitions.len()];
+        counts[self.initial] = 1;
+
+        for _ in 0..len {
+            let mut next = vec![0u128; self.transitions.len()];
+            for (state, map) in self.transitions.iter().enumerate() {
+                if counts[state] == 0 {
+                    continue;
+                }
+                for v in &self.alphabet {
+                    if let Some(&t) = map.get(v) {
+                        next[t] = next[t].saturating_add(counts[state]);
+                    }
+                }
+            }
+            counts = next;
+        }
+
+        self.finals
+            .iter()
+            .fold(0, |acc: u128, x| acc.saturating_add(counts[*x]))
+    }
+
+    /// Returns the number of words accepted by `self` if it is finite, `None` otherwise.
+    /// The count saturates at `usize::MAX` instead of overflowing.
+    pub fn count_accepted(&self) -> Option<usize> {
+        if !self.is_finite() {
+            return None;
+        }
+
+        // the trimmed automaton is acyclic since the language is finite
+        let dfa = self.clone().trim();
+        let l = dfa.transitions.len();
+
+        let mut degrees = vec![0; l];
+        for map in &dfa.transitions {
+            for t in map.values() {
+                degrees[*t] += 1;
+            }
+        }
+
+        let mut counts = vec![0usize; l];
+        counts[dfa.initial] = 1;
+        let mut stack: Vec<usize> = (0..l).filter(|x| degrees[*x] == 0).collect();
+        while let Some(e) = stack.pop() {
+            for t in dfa.transitions[e].values() {
+                counts[*t] = counts[*t].saturating_add(counts[e]);
+                degrees[*t] -= 1;
+                if degrees[*t] == 0 {
+                    stack.push(*t);
+                }
+            }
+        }
+
+        Some(
+            dfa.finals
+                .iter()
+                .fold(0, |acc: usize, x| acc.saturating_add(counts[*x])),
+        )
+    }
+
+    /// Returns the states reached after each prefix of `word`, starting with the empty prefix, and whether `word` is accepted.
+    /// The trace stops early at a missing transition, in which case `word` is rejected.
+    pub fn run_trace(&self, word: &[V]) -> (Vec<usize>, bool) {
+        let mut trace = Vec::with_capacity(word.len() + 1);
+        let mut actual = self.initial;
+        trace.push(actual);
+        for l in word {
+            if let Some(t) = self.transitions[actual].get(l) {
+                actual = *t;
+                trace.push(actual);
+            } else {
+                return (trace, false);
+            }
+        }
+        (trace, self.finals.contains(&actual))
+    }
+
+    /// Returns `true` if and only if some suffix of `word`, possibly empty or `word` itself, is accepted by `self`, like [`str::ends_with`](https://doc.rust-lang.org/std/primitive.str.html#method.ends_with).
+    /// This is the membership of `word` in the language of `.*` concatenated with `self`, not in the [`suffix_closure`](../nfa/struct.NFA.html#method.suffix_closure) of `self`.
+    pub fn accepts_suffix(&self, word: &[V]) -> bool {
+        // the states reached by the suffixes starting at each position read so far
+        let mut actuals = HashSet::new();
+        actuals.insert(self.initial);
+        for l in word {
+            actuals = actuals
+                .iter()
+                .filter_map(|x| self.transitions[*x].get(l).cloned())
+                .collect();
+            actuals.insert(self.initial);
+        }
+        actuals.iter().any(|x| self.finals.contains(x))
+    }
+
+    /// Returns `true` if and only if some factor of `word`, possibly empty or `word` itself, is accepted by `self`, like [`str::contains`](https://doc.rust-lang.org/std/primitive.str.html#method.contains).
+    pub fn accepts_substring(&self, word: &[V]) -> bool {
+        let mut actuals = HashSet::new();
+        actuals.insert(self.initial);
+        for l in word {
+            if actuals.iter().any(|x| self.finals.contains(x)) {
+                return true;
+            }
+            actuals = actuals
+                .iter()
+                .filter_map(|x| self.transitions[*x].get(l).cloned())
+                .collect();
+            actuals.insert(self.initial);
+        }
+        actuals.iter().any(|x| self.finals.contains(x))
+    }
+
+    /// Returns the length of the longest prefix of `word` accepted by `self`, or `None` if no prefix is accepted, not even the empty one.
+    pub fn longest_match(&self, word: &[V]) -> Option<usize> {
+        let mut actual = self.initial;
+        let mut last = if self.finals.contains(&actual) {
+            Some(0)
+        } else {
+            None
+        };
+
+        for (i, l) in word.iter().enumerate() {
+            match self.transitions[actual].get(l) {
+                Some(t) => actual = *t,
+                None => break,
+            }
+            if self.finals.contains(&actual) {
+                last = Some(i + 1);
+            }
+        }
+
+        last
+    }
+
+    /// Returns an iterator over the words accepted by `self`, in length-lexicographic order.
+    /// The iterator is lazy so it can be used on infinite languages with `take`.
+    pub fn words(&self) -> impl Iterator<Item = Vec<V>> {
+        // only keeping the states that lead to a final state ensures that the iteration terminates on finite languages
+        let dfa = self.clone().make_coreachable();
+        let mut alphabet: Vec<V> = dfa.alphabet.iter().cloned().collect();
+        alphabet.sort();
+
+        let mut queue = VecDeque::new();
+        queue.push_back((dfa.initial, Vec::new()));
+
+        from_fn(move || {
+            while let Some((state, word)) = queue.pop_front() {
+                for v in &alphabet {
+                    if let Some(&t) = dfa.transitions[state].get(v) {
+                        let mut next = word.clone();
+                        next.push(v.clone());
+                        queue.push_back((t, next));
+                    }
+                }
+                if dfa.finals.contains(&state) {
+                    return Some(word);
+                }
+            }
+            None
+        })
+    }
+
+    /// Returns `true` if and only if no word accepted by `self` is a proper prefix of another accepted word.
+    pub fn is_prefix_free(&self) -> bool {
+        // in a trimmed automaton any transition leads to a final state,
+        // so a final state has a path to a final state if and only if it has an outgoing transition
+        let dfa = self.clone().trim();
+        dfa.finals.iter().all(|x| dfa.transitions[*x].is_empty())
+    }
+
+    /// Returns the words of length at most `max_len` accepted by `self`, in length-lexicographic order.
+    pub fn words_up_to(&self, max_len: usize) -> Vec<Vec<V>> {
+        // the words reaching a state that leads to no final state aren't worth extending
+        let dfa = self.clone().make_coreachable();
+        let mut alphabet: Vec<V> = dfa.alphabet.iter().cloned().collect();
+        alphabet.sort();
+
+        let mut words = Vec::new();
+        let mut layer = vec![(dfa.initial, Vec::new())];
+        for len in 0..=max_len {
+            let mut next = Vec::new();
+            for (state, word) in layer {
+                if len < max_len {
+                    for v in &alphabet {
+                        if let Some(&t) = dfa.transitions[state].get(v) {
+                            let mut w = word.clone();
+                            w.push(v.clone());
+                            next.push((t, w));
+                        }
+                    }
+                }
+                if dfa.finals.contains(&state) {
+                    words.push(word);
+                }
+            }
+            layer = next;
+        }
+
+        words
     }
 
     /// Returns an empty automaton with the given alphabet.
@@ -74,14 +846,18 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> DFA<V> {
         }
 
         for (state, map) in transitions.iter().enumerate() {
-            if let Some(&letter) = map.keys().find(|&x| !alphabet.contains(x)) {
-                return Err(FromRawError::UnknownLetter(letter));
+            if let Some(letter) = map.keys().find(|&x| !alphabet.contains(x)) {
+                return Err(FromRawError::UnknownLetter(letter.clone()));
             }
 
-            if let Some((&letter, &destination)) =
+            if let Some((letter, &destination)) =
                 map.iter().find(|(_, &destination)| destination >= len)
             {
-                return Err(FromRawError::InvalidTransition(state, letter, destination));
+                return Err(FromRawError::InvalidTransition(
+                    state,
+                    letter.clone(),
+                    destination,
+                ));
             }
         }
 
@@ -94,17 +870,83 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> DFA<V> {
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for DFA<V> {
-    fn run(&self, v: &[V]) -> bool {
+#[cfg(feature = "rand")]
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> DFA<V> {
+    /// Returns a word of length `len` drawn uniformly at random among the words of length `len` accepted by `self`, or `None` if there is no such word.
+    /// The distribution is only approximately uniform if there are more than `u128::MAX` such words.
+    pub fn sample_word<R: Rng + ?Sized>(&self, len: usize, rng: &mut R) -> Option<Vec<V>> {
+        let mut alphabet: Vec<_> = self.alphabet.iter().cloned().collect();
+        alphabet.sort();
+
+        // counts[k][state] is the number of words of length k accepted from state
+        let mut counts = Vec::with_capacity(len + 1);
+        counts.push(
+            (0..self.transitions.len())
+                .map(|x| if self.finals.contains(&x) { 1u128 } else { 0 })
+                .collect::<Vec<_>>(),
+        );
+        for k in 0..len {
+            let next = self
+                .transitions
+                .iter()
+                .map(|map| {
+                    map.values()
+                        .fold(0u128, |acc, t| acc.saturating_add(counts[k][*t]))
+                })
+                .collect();
+            counts.push(next);
+        }
+
         let mut actual = self.initial;
-        for l in v {
-            if let Some(t) = self.transitions[actual].get(l) {
-                actual = *t;
-            } else {
-                return false;
+        if counts[len][actual] == 0 {
+            return None;
+        }
+
+        let mut word = Vec::with_capacity(len);
+        for k in (0..len).rev() {
+            let mut choice = rng.gen_range(0, counts[k + 1][actual]);
+            for v in &alphabet {
+                if let Some(&t) = self.transitions[actual].get(v) {
+                    if choice < counts[k][t] {
+                        word.push(v.clone());
+                        actual = t;
+                        break;
+                    }
+                    choice -= counts[k][t];
+                }
             }
         }
-        self.finals.contains(&actual)
+
+        Some(word)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<V: Eq + Hash + Display + Clone + Debug + Ord + Serialize> DFA<V> {
+    /// Returns the JSON representation of `self`, listing its alphabet, its initial state, its final states and the transitions of each state.
+    ///
+    /// # Panics
+    /// Panics if the letters can't be used as keys of a JSON object.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("letters can't be used as JSON keys")
+    }
+}
+
+#[cfg(feature = "json")]
+impl<V: Eq + Hash + Display + Clone + Debug + Ord + DeserializeOwned> DFA<V> {
+    /// Returns the automaton described by the given JSON, as produced by [`to_json`](#method.to_json).
+    pub fn from_json(json: &str) -> Result<DFA<V>, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Automata<V> for DFA<V> {
+    fn run(&self, v: &[V]) -> bool {
+        self.run_letters(v)
+    }
+
+    fn run_iter<I: IntoIterator<Item = V>>(&self, word: I) -> bool {
+        self.run_letters(word)
     }
 
     fn is_complete(&self) -> bool {
@@ -135,11 +977,11 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for DFA<V>
     }
 
     fn is_coreachable(&self) -> bool {
-        self.to_nfa().is_coreachable()
+        self.alive_states().len() == self.transitions.len()
     }
 
     fn is_trimmed(&self) -> bool {
-        self.to_nfa().is_trimmed()
+        self.is_reachable() && self.is_coreachable()
     }
 
     fn is_empty(&self) -> bool {
@@ -150,12 +992,8 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for DFA<V>
         self.to_nfa().is_full()
     }
 
-    fn negate(mut self) -> DFA<V> {
-        self = self.complete();
-        self.finals = (0..self.transitions.len())
-            .filter(|x| !self.finals.contains(&x))
-            .collect();
-        self
+    fn negate(self) -> DFA<V> {
+        self.complement()
     }
 
     fn complete(mut self) -> DFA<V> {
@@ -168,7 +1006,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for DFA<V>
         for map in &mut self.transitions {
             for v in &self.alphabet {
                 if !map.contains_key(&v) {
-                    map.insert(*v, l);
+                    map.insert(v.clone(), l);
                 }
             }
         }
@@ -185,6 +1023,10 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for DFA<V>
     }
 
     fn trim(self) -> DFA<V> {
+        if self.is_trimmed() {
+            return self;
+        }
+
         self.to_nfa().trim().to_dfa()
     }
 
@@ -193,7 +1035,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for DFA<V>
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Buildable<V> for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Buildable<V> for DFA<V> {
     fn unite(self, b: DFA<V>) -> DFA<V> {
         self.to_nfa().unite(b.to_nfa()).to_dfa()
     }
@@ -219,54 +1061,79 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Buildable<V> for DFA<V
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> ToDfa<V> for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> ToDfa<V> for DFA<V> {
     fn to_dfa(&self) -> DFA<V> {
         self.clone()
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> ToRegex<V> for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> ToRegex<V> for DFA<V> {
     fn to_regex(&self) -> Regex<V> {
         self.to_nfa().to_regex()
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> ToNfa<V> for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> ToNfa<V> for DFA<V> {
     fn to_nfa(&self) -> NFA<V> {
         let mut initials = HashSet::new();
         initials.insert(self.initial);
         let mut transitions = Vec::new();
         for map in &self.transitions {
-            transitions.push(map.iter().map(|(k, v)| (*k, vec![*v])).collect());
+            transitions.push(map.iter().map(|(k, v)| (k.clone(), vec![*v])).collect());
         }
         NFA {
             alphabet: self.alphabet.clone(),
             initials,
             finals: self.finals.clone(),
+            epsilon: (0..transitions.len()).map(|_| HashSet::new()).collect(),
             transitions,
         }
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<DFA<V>> for DFA<V> {
+/// Displays the transition table of the automaton, with a row per state and a column per letter.
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Display for DFA<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut alphabet: Vec<_> = self.alphabet.iter().collect();
+        alphabet.sort();
+
+        let header = alphabet.iter().map(|x| x.to_string()).collect();
+        let rows = self
+            .transitions
+            .iter()
+            .enumerate()
+            .map(|(i, map)| {
+                let cells = alphabet
+                    .iter()
+                    .map(|v| map.get(v).map(|x| x.to_string()).unwrap_or_default())
+                    .collect();
+                (i, i == self.initial, self.finals.contains(&i), cells)
+            })
+            .collect();
+
+        write_table(f, header, rows)
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<DFA<V>> for DFA<V> {
     fn eq(&self, b: &DFA<V>) -> bool {
         self.le(&b) && self.ge(&b)
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<NFA<V>> for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<NFA<V>> for DFA<V> {
     fn eq(&self, b: &NFA<V>) -> bool {
         self.to_nfa().eq(b)
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<Regex<V>> for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<Regex<V>> for DFA<V> {
     fn eq(&self, b: &Regex<V>) -> bool {
         self.to_nfa().eq(&b.to_nfa())
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<Automaton<V>> for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<Automaton<V>> for DFA<V> {
     fn eq(&self, b: &Automaton<V>) -> bool {
         match b {
             Automaton::DFA(v) => self.eq(&*v),
@@ -276,7 +1143,26 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<Automaton<V>
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialOrd for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Eq for DFA<V> {}
+
+/// The hash of A is computed on A.canonical() so that two automata accepting the same words have the same hash.
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Hash for DFA<V> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let dfa = self.clone().canonical();
+
+        let mut finals: Vec<usize> = dfa.finals.into_iter().collect();
+        finals.sort();
+        finals.hash(state);
+
+        for map in dfa.transitions {
+            let mut map: Vec<(V, usize)> = map.into_iter().collect();
+            map.sort();
+            map.hash(state);
+        }
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialOrd for DFA<V> {
     fn partial_cmp(&self, other: &DFA<V>) -> Option<Ordering> {
         match (self.ge(&other), self.le(&other)) {
             (true, true) => Some(Equal),
@@ -303,8 +1189,114 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialOrd for DFA<V>
     }
 }
 
+impl<'a, V: Eq + Hash + Display + Clone + Debug + Ord> DfaRunner<'a, V> {
+    /// Reads `letter` and returns `true` if and only if no word can be accepted anymore, in which case the rest of the input can be skipped.
+    pub fn feed(&mut self, letter: V) -> bool {
+        let transitions = &self.dfa.transitions;
+        self.state = self
+            .state
+            .and_then(|state| transitions[state].get(&letter).cloned());
+        self.is_dead()
+    }
+
+    /// Returns `true` if and only if the letters read so far form a word accepted by the automaton.
+    pub fn is_accepting(&self) -> bool {
+        self.state.map_or(false, |x| self.dfa.finals.contains(&x))
+    }
+
+    /// Returns `true` if and only if no continuation of the letters read so far is accepted.
+    pub fn is_dead(&self) -> bool {
+        !self.state.map_or(false, |x| self.alive.contains(&x))
+    }
+
+    /// Returns the current state, or `None` if a transition was missing.
+    pub fn state(&self) -> Option<usize> {
+        self.state
+    }
+
+    /// Goes back to the initial state, as if no letter had been read.
+    pub fn reset(&mut self) {
+        self.state = Some(self.dfa.initial);
+    }
+}
+
+impl DFA<char> {
+    /// Returns `true` if and only if `self` accepts the characters of `s`.
+    pub fn run_str(&self, s: &str) -> bool {
+        self.run(&s.chars().collect::<Vec<_>>())
+    }
+
+    /// An alias for [`run_str`](#method.run_str).
+    pub fn is_match(&self, s: &str) -> bool {
+        self.run_str(s)
+    }
+
+    /// Returns the automaton described by the given CSV transition table, as produced by [`to_csv`](#method.to_csv).
+    pub fn from_csv(csv: &str) -> Result<DFA<char>, String> {
+        let mut lines = csv.lines().filter(|x| !x.trim().is_empty());
+
+        let header = csv_fields(lines.next().ok_or_else(|| "Missing header".to_string())?)?;
+        if header[0] != "state" {
+            return Err(format!("Invalid header {}", header[0]));
+        }
+        let mut letters = Vec::new();
+        for x in &header[1..] {
+            let mut chars = x.chars();
+            match (chars.next(), chars.next()) {
+                (Some(c), None) if !letters.contains(&c) => letters.push(c),
+                _ => return Err(format!("Invalid letter {}", x)),
+            }
+        }
+
+        let mut initial = None;
+        let mut finals = HashSet::new();
+        let mut transitions = Vec::new();
+        for (i, line) in lines.enumerate() {
+            let fields = csv_fields(line)?;
+            if fields.len() != header.len() {
+                return Err(format!(
+                    "Row {} has {} cells instead of {}",
+                    i,
+                    fields.len(),
+                    header.len()
+                ));
+            }
+
+            let mut state = fields[0].as_str();
+            if let Some(x) = state.strip_prefix("->") {
+                if initial.replace(i).is_some() {
+                    return Err("Several initial states".to_string());
+                }
+                state = x;
+            }
+            if let Some(x) = state.strip_prefix('*') {
+                finals.insert(i);
+                state = x;
+            }
+            if state.parse::<usize>() != Ok(i) {
+                return Err(format!("Row {} is labeled by {}", i, fields[0]));
+            }
+
+            let mut map = HashMap::new();
+            for (v, t) in letters.iter().zip(&fields[1..]) {
+                if !t.is_empty() {
+                    let t = t
+                        .parse::<usize>()
+                        .map_err(|_| format!("Invalid state {}", t))?;
+                    map.insert(*v, t);
+                }
+            }
+            transitions.push(map);
+        }
+
+        let initial = initial.ok_or_else(|| "Missing initial state".to_string())?;
+        DFA::from_raw(letters.into_iter().collect(), initial, finals, transitions)
+            .map_err(|e| e.to_string())
+    }
+}
+
 impl FromStr for DFA<char> {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<DFA<char>, Self::Err> {
         NFA::from_str(s).map(|x| x.to_dfa())
@@ -312,7 +1304,7 @@ impl FromStr for DFA<char> {
 }
 
 /// The multiplication of A and B is A.concatenate(B)
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Mul for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Mul for DFA<V> {
     type Output = Self;
 
     fn mul(self, other: DFA<V>) -> DFA<V> {
@@ -321,7 +1313,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Mul for DFA<V> {
 }
 
 /// The negation of A is A.negate().
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Neg for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Neg for DFA<V> {
     type Output = Self;
 
     fn neg(self) -> DFA<V> {
@@ -330,7 +1322,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Neg for DFA<V> {
 }
 
 /// The opposite of A is A.reverse().
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Not for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Not for DFA<V> {
     type Output = Self;
 
     fn not(self) -> DFA<V> {
@@ -339,7 +1331,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Not for DFA<V> {
 }
 
 /// The substraction of A and B is an automaton that accepts a word if and only if A accepts it and B doesn't.
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Sub for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Sub for DFA<V> {
     type Output = Self;
 
     fn sub(self, other: DFA<V>) -> DFA<V> {
@@ -348,10 +1340,28 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Sub for DFA<V> {
 }
 
 /// The addition fo A and B is an automaton that accepts a word if and only if A or B accept it.
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Add for DFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Add for DFA<V> {
     type Output = Self;
 
     fn add(self, other: DFA<V>) -> DFA<V> {
         self.unite(other)
     }
 }
+
+/// The bitwise and of A and B is A.intersect(B).
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> BitAnd for DFA<V> {
+    type Output = Self;
+
+    fn bitand(self, other: DFA<V>) -> DFA<V> {
+        self.intersect(other)
+    }
+}
+
+/// The bitwise or of A and B is A.unite(B), like their addition.
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> BitOr for DFA<V> {
+    type Output = Self;
+
+    fn bitor(self, other: DFA<V>) -> DFA<V> {
+        self.unite(other)
+    }
+}
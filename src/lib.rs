@@ -1,8 +1,24 @@
+//! <https://en.wikipedia.org/wiki/Regular_language>
+//! <https://en.wikipedia.org/wiki/Finite-state_machine>
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(not(feature = "std"))]
+extern crate alloc;
 extern crate logos;
 
-/// <https://en.wikipedia.org/wiki/Regular_language>
-/// <https://en.wikipedia.org/wiki/Finite-state_machine>
+#[cfg(all(feature = "serde", not(feature = "std")))]
+compile_error!("the `serde` feature requires the `std` feature");
+#[cfg(all(feature = "rand", not(feature = "std")))]
+compile_error!("the `rand` feature requires the `std` feature");
+#[cfg(all(feature = "rayon", not(feature = "std")))]
+compile_error!("the `rayon` feature requires the `std` feature");
+#[cfg(all(feature = "proptest", not(feature = "std")))]
+compile_error!("the `proptest` feature requires the `std` feature");
+#[cfg(not(any(feature = "std", feature = "hashbrown")))]
+compile_error!("without the `std` feature, the `alloc` feature is required");
 
+mod compat;
 #[macro_use]
 mod utils;
 
@@ -1,44 +1,747 @@
 use crate::{
     automaton::{Automata, Automaton, Buildable, FromRawError},
+    compat::*,
     dfa::{ToDfa, DFA},
-    regex::{Operations, Regex, ToRegex},
+    regex::{Operations, ParseError, Regex, ToRegex},
     utils::*,
 };
-use std::{
+#[cfg(feature = "bitvec")]
+use bitvec::prelude::*;
+#[cfg(feature = "serde")]
+use core::convert::TryFrom;
+use core::{
+    borrow::Borrow,
+    cell::Cell,
     cmp::{Ordering, Ordering::*, PartialEq, PartialOrd},
-    collections::{BTreeSet, HashMap, HashSet, VecDeque},
-    fmt::{Debug, Display},
+    fmt::{self, Debug, Display, Formatter},
     hash::Hash,
-    iter::{repeat, FromIterator},
-    ops::{Add, BitOr, Bound::*, Mul, Neg, Not, RangeBounds, Sub},
+    iter::FromIterator,
+    ops::{Add, BitAnd, BitOr, Bound::*, Mul, Neg, Not, RangeBounds, Sub},
     str::FromStr,
 };
+#[cfg(feature = "proptest")]
+use proptest::{collection, prelude::*};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+#[cfg(feature = "json")]
+use serde::de::DeserializeOwned;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 /// <https://en.wikipedia.org/wiki/Nondeterministic_finite_automaton>
 #[derive(Debug, Clone)]
-pub struct NFA<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "serde", serde(try_from = "RawNFA<V>"))]
+pub struct NFA<V: Eq + Hash + Display + Clone + Debug + Ord> {
     pub(crate) alphabet: HashSet<V>,
     pub(crate) initials: HashSet<usize>,
     pub(crate) finals: HashSet<usize>,
     pub(crate) transitions: Vec<HashMap<V, Vec<usize>>>,
+    pub(crate) epsilon: Vec<HashSet<usize>>,
+}
+
+/// The unchecked fields of a deserialized NFA, validated by [`NFA::from_raw`](struct.NFA.html#method.from_raw).
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct RawNFA<V: Eq + Hash + Display + Clone + Debug + Ord> {
+    alphabet: HashSet<V>,
+    initials: HashSet<usize>,
+    finals: HashSet<usize>,
+    transitions: Vec<HashMap<V, Vec<usize>>>,
+    #[serde(default)]
+    epsilon: Vec<HashSet<usize>>,
+}
+
+/// A builder to construct a [`NFA`](struct.NFA.html) step by step.
+#[derive(Debug, Clone)]
+pub struct NFABuilder<V: Eq + Hash + Display + Clone + Debug + Ord> {
+    alphabet: HashSet<V>,
+    initials: HashSet<usize>,
+    finals: HashSet<usize>,
+    transitions: Vec<HashMap<V, Vec<usize>>>,
+}
+
+/// The options of [`NFA::to_dot_opts`](struct.NFA.html#method.to_dot_opts).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DotOptions {
+    /// Collapse runs of consecutive characters into ranges like `0-9` in edge labels.
+    pub compact_ranges: bool,
+}
+
+/// The statistics of a subset construction, returned by [`NFA::to_dfa_with_stats`](struct.NFA.html#method.to_dfa_with_stats).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DeterminizationStats {
+    /// The number of sets of states created, which is the number of states of the DFA.
+    pub subsets: usize,
+    /// The size of the largest set of states encountered.
+    pub max_subset_size: usize,
 }
 
 /// An interface for structs that can be converted into a NFA.
-pub trait ToNfa<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> {
+pub trait ToNfa<V: Eq + Hash + Display + Clone + Debug + Ord> {
     fn to_nfa(&self) -> NFA<V>;
 }
 
+#[cfg(feature = "serde")]
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> TryFrom<RawNFA<V>> for NFA<V> {
+    type Error = FromRawError<V>;
+
+    fn try_from(raw: RawNFA<V>) -> Result<NFA<V>, FromRawError<V>> {
+        let mut nfa = NFA::from_raw(raw.alphabet, raw.initials, raw.finals, raw.transitions)?;
+        let len = nfa.transitions.len();
+        for (from, targets) in raw.epsilon.into_iter().enumerate() {
+            for to in targets {
+                if from >= len || to >= len {
+                    return Err(FromRawError::InvalidEpsilonTransition(from, to));
+                }
+                nfa.epsilon[from].insert(to);
+            }
+        }
+        Ok(nfa)
+    }
+}
+
+/* IMPLEMENTATION OF NFABUILDER */
+
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> NFABuilder<V> {
+    /// Returns a builder without any state over the given alphabet.
+    pub fn new(alphabet: HashSet<V>) -> NFABuilder<V> {
+        NFABuilder {
+            alphabet,
+            initials: HashSet::new(),
+            finals: HashSet::new(),
+            transitions: Vec::new(),
+        }
+    }
+
+    /// Adds a new state without any transition.
+    pub fn state(mut self) -> NFABuilder<V> {
+        self.transitions.push(HashMap::new());
+        self
+    }
+
+    /// Adds a transition from `from` to `to` labeled by `letter`, adding the missing states if needed.
+    pub fn transition(mut self, from: usize, letter: V, to: usize) -> NFABuilder<V> {
+        let len = from.max(to) + 1;
+        if self.transitions.len() < len {
+            self.transitions.resize_with(len, HashMap::new);
+        }
+
+        let targets = self.transitions[from].entry(letter).or_default();
+        if !targets.contains(&to) {
+            targets.push(to);
+        }
+        self
+    }
+
+    /// Makes `state` an initial state.
+    pub fn initial(mut self, state: usize) -> NFABuilder<V> {
+        self.initials.insert(state);
+        self
+    }
+
+    /// Makes `state` a final state.
+    pub fn final_state(mut self, state: usize) -> NFABuilder<V> {
+        self.finals.insert(state);
+        self
+    }
+
+    /// Returns the built automaton, with the same checks as [`NFA::from_raw`](struct.NFA.html#method.from_raw).
+    pub fn build(self) -> Result<NFA<V>, FromRawError<V>> {
+        NFA::from_raw(self.alphabet, self.initials, self.finals, self.transitions)
+    }
+}
+
 /* IMPLEMENTATION OF NFA */
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> NFA<V> {
+    /// Returns an NFA that accepts a word if and only if `self` doesn't accept this word, same as [`negate`](#method.negate).
+    /// An NFA can't be complemented by swapping its final states, so it is determinized first.
+    pub fn complement(self) -> NFA<V> {
+        self.negate()
+    }
+
+    /// Same as [`unite`](../automaton/trait.Buildable.html#tymethod.unite), but builds a new NFA without consuming `self` nor `other`.
+    pub fn union_ref(&self, other: &NFA<V>) -> NFA<V> {
+        self.clone().unite(other.clone())
+    }
+
+    /// Same as [`concatenate`](../automaton/trait.Buildable.html#tymethod.concatenate), but builds a new NFA without consuming `self` nor `other`.
+    pub fn concat_ref(&self, other: &NFA<V>) -> NFA<V> {
+        self.clone().concatenate(other.clone())
+    }
+
     /// Returns an NFA that accepts a word if and only if this word is accepted by both `self` and `other`.
     pub fn intersect(self, other: NFA<V>) -> NFA<V> {
-        self.negate().unite(other.negate()).negate().to_nfa()
+        self.product(&other, |a, b| a && b)
+    }
+
+    /// Returns the product of `self` and `other`: its states are the pairs of states `(i, j)` reachable by reading the same letters in both automata, and `accept` decides if `(i, j)` is final given whether `i` and `j` are.
+    /// A word is accepted if it leads to an accepting pair, so for `accept` other than a conjunction the result is only meaningful if both automata are deterministic and [`complete`](../automaton/trait.Automata.html#complete-automaton) over the same alphabet, since otherwise a word may lead to no pair or to several.
+    pub fn product<F: Fn(bool, bool) -> bool>(self, other: &NFA<V>, accept: F) -> NFA<V> {
+        if self.has_epsilon() || other.has_epsilon() {
+            return self
+                .remove_epsilon()
+                .product(&other.clone().remove_epsilon(), accept);
+        }
+
+        let mut map = HashMap::new();
+        let mut stack = Vec::new();
+
+        let mut nfa = NFA::new_empty(self.alphabet.union(&other.alphabet).cloned().collect());
+
+        for &i in &self.initials {
+            for &j in &other.initials {
+                let l = nfa.add_state();
+                map.insert((i, j), l);
+                nfa.initials.insert(l);
+                stack.push((i, j));
+            }
+        }
+
+        while let Some((i, j)) = stack.pop() {
+            let num = *map.get(&(i, j)).unwrap();
+            if accept(self.finals.contains(&i), other.finals.contains(&j)) {
+                nfa.finals.insert(num);
+            }
+
+            for (v, ti) in &self.transitions[i] {
+                if let Some(tj) = other.transitions[j].get(v) {
+                    let mut targets = Vec::new();
+                    for &x in ti {
+                        for &y in tj {
+                            let val = *map.entry((x, y)).or_insert_with(|| {
+                                stack.push((x, y));
+                                nfa.add_state()
+                            });
+                            targets.push(val);
+                        }
+                    }
+                    nfa.transitions[num].insert(v.clone(), targets);
+                }
+            }
+        }
+
+        nfa
+    }
+
+    /// Returns an NFA that accepts a word if and only if this word is accepted by `self` and not by `other`.
+    pub fn difference(self, other: NFA<V>) -> NFA<V> {
+        if self.has_epsilon() {
+            return self.remove_epsilon().difference(other);
+        }
+
+        // only `other` needs to be determinized (and completed) to be complemented
+        let mut dfa = other.to_dfa();
+        append_hashset(&mut dfa.alphabet, self.alphabet.clone());
+        let dfa = dfa.complete();
+
+        let mut map = HashMap::new();
+        let mut stack = Vec::new();
+
+        let mut nfa = NFA::new_empty(dfa.alphabet.clone());
+
+        for &i in &self.initials {
+            let l = nfa.add_state();
+            map.insert((i, dfa.initial), l);
+            nfa.initials.insert(l);
+            stack.push((i, dfa.initial));
+        }
+
+        while let Some((i, j)) = stack.pop() {
+            let num = *map.get(&(i, j)).unwrap();
+            if self.finals.contains(&i) && !dfa.finals.contains(&j) {
+                nfa.finals.insert(num);
+            }
+
+            for (v, ti) in &self.transitions[i] {
+                let y = *dfa.transitions[j].get(v).unwrap();
+                let mut targets = Vec::new();
+                for &x in ti {
+                    let val = *map.entry((x, y)).or_insert_with(|| {
+                        stack.push((x, y));
+                        nfa.add_state()
+                    });
+                    targets.push(val);
+                }
+                nfa.transitions[num].insert(v.clone(), targets);
+            }
+        }
+
+        nfa
+    }
+
+    /// Returns an NFA that accepts a word if and only if this word is an interleaving of a word accepted by `self` and of a word accepted by `other`.
+    pub fn shuffle(self, other: NFA<V>) -> NFA<V> {
+        if self.has_epsilon() || other.has_epsilon() {
+            return self.remove_epsilon().shuffle(other.remove_epsilon());
+        }
+
+        let mut map = HashMap::new();
+        let mut stack = Vec::new();
+
+        let mut nfa = NFA::new_empty(self.alphabet.union(&other.alphabet).cloned().collect());
+
+        for &i in &self.initials {
+            for &j in &other.initials {
+                let l = nfa.add_state();
+                map.insert((i, j), l);
+                nfa.initials.insert(l);
+                stack.push((i, j));
+            }
+        }
+
+        while let Some((i, j)) = stack.pop() {
+            let num = *map.get(&(i, j)).unwrap();
+            if self.finals.contains(&i) && other.finals.contains(&j) {
+                nfa.finals.insert(num);
+            }
+
+            let left = self.transitions[i]
+                .iter()
+                .flat_map(|(v, t)| t.iter().map(move |&x| (v.clone(), (x, j))));
+            let right = other.transitions[j]
+                .iter()
+                .flat_map(|(v, t)| t.iter().map(move |&y| (v.clone(), (i, y))));
+            for (v, pair) in left.chain(right).collect::<Vec<_>>() {
+                let val = *map.entry(pair).or_insert_with(|| {
+                    stack.push(pair);
+                    nfa.add_state()
+                });
+                let targets = nfa.transitions[num].entry(v).or_insert_with(Vec::new);
+                if !targets.contains(&val) {
+                    targets.push(val);
+                }
+            }
+        }
+
+        nfa
+    }
+
+    /// Returns an NFA that accepts a word if and only if this word is accepted by exactly one of `self` and `other`.
+    pub fn symmetric_difference(self, other: NFA<V>) -> NFA<V> {
+        (self.clone() - other.clone()) + (other - self)
     }
 
     /// A contains B if and only if for each `word` w, if B `accepts` w then A `accepts` w.
+    /// `self` isn't determinized when either side is empty, or when `self` has an initial final state looping on every letter of `other`.
     pub fn contains(&self, other: &NFA<V>) -> bool {
-        self.clone().negate().intersect(other.clone()).is_empty()
+        self.contains_witness(other).is_ok()
+    }
+
+    /// Same as [`contains`](#method.contains), but if `self` doesn't contain `other` then the shortest word accepted by `other` and not by `self` is returned.
+    pub fn contains_witness(&self, other: &NFA<V>) -> Result<(), Vec<V>> {
+        // the empty and (trivially) full cases don't need `self` to be determinized
+        if other.is_empty() || self.has_full_loop(&other.alphabet) {
+            return Ok(());
+        } else if self.is_empty() {
+            return Err(other.shortest_accepted().unwrap());
+        }
+
+        let mut complement = self.clone();
+        append_hashset(&mut complement.alphabet, other.alphabet.clone());
+        match complement
+            .negate()
+            .intersect(other.clone())
+            .shortest_accepted()
+        {
+            Some(word) => Err(word),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns `true` if an initial state of `self` is final and loops on every letter of `alphabet`, in which case `self` accepts every word over `alphabet`.
+    /// Unlike [`is_full`](#method.is_full) it doesn't explore the subsets of states, so a `false` is inconclusive.
+    fn has_full_loop(&self, alphabet: &HashSet<V>) -> bool {
+        self.initials.iter().any(|&i| {
+            self.finals.contains(&i)
+                && alphabet.iter().all(|v| {
+                    self.transitions[i]
+                        .get(v)
+                        .map_or(false, |targets| targets.contains(&i))
+                })
+        })
+    }
+
+    /// Returns `true` if and only if no word is accepted by both `self` and `other`, without determinizing them.
+    pub fn is_disjoint(&self, other: &NFA<V>) -> bool {
+        self.clone().product(other, |a, b| a && b).is_empty()
+    }
+
+    /// Returns the shortest word accepted by both `self` and `other`, or `None` if they are [`disjoint`](#method.is_disjoint).
+    pub fn intersection_witness(&self, other: &NFA<V>) -> Option<Vec<V>> {
+        self.clone()
+            .product(other, |a, b| a && b)
+            .shortest_accepted()
+    }
+
+    /// Returns an NFA that accepts a word w if and only if `self` accepts the concatenation of `prefix` and w.
+    pub fn left_quotient(&self, prefix: &[V]) -> NFA<V> {
+        let mut actuals = self.epsilon_closure(&self.initials);
+        for l in prefix {
+            actuals = self.epsilon_closure(
+                &actuals
+                    .iter()
+                    .filter_map(|st| self.transitions[*st].get(l))
+                    .flatten()
+                    .cloned()
+                    .collect(),
+            );
+            if actuals.is_empty() {
+                return NFA::new_empty(self.alphabet.clone());
+            }
+        }
+
+        let mut nfa = self.clone();
+        nfa.initials = actuals;
+        nfa
+    }
+
+    /// Returns an NFA that accepts a word if and only if it is a prefix of a word accepted by `self`.
+    pub fn prefix_closure(self) -> NFA<V> {
+        let mut nfa = self.trim();
+        // every state of the trimmed automaton leads to a final state
+        nfa.finals = (0..nfa.transitions.len()).collect();
+        nfa
+    }
+
+    /// Returns an NFA that accepts a word if and only if it is a suffix of a word accepted by `self`.
+    pub fn suffix_closure(self) -> NFA<V> {
+        let mut nfa = self.trim();
+        // every state of the trimmed automaton is reachable from an initial state
+        nfa.initials = (0..nfa.transitions.len()).collect();
+        nfa
+    }
+
+    /// Returns an NFA that accepts a word if and only if it is a factor of a word accepted by `self`.
+    pub fn factor_closure(self) -> NFA<V> {
+        let mut nfa = self.trim();
+        nfa.initials = (0..nfa.transitions.len()).collect();
+        nfa.finals = nfa.initials.clone();
+        nfa
+    }
+
+    /// Returns the NFA obtained by renaming each letter `v` of `self` into `f(v)`.
+    /// If several letters are mapped to the same one, their transitions are merged.
+    pub fn map_alphabet<W, F>(self, f: F) -> NFA<W>
+    where
+        W: Eq + Hash + Display + Clone + Debug + Ord,
+        F: Fn(V) -> W,
+    {
+        let NFA {
+            alphabet,
+            initials,
+            finals,
+            transitions,
+            epsilon,
+        } = self;
+
+        let transitions = transitions
+            .into_iter()
+            .map(|map| {
+                let mut new_map: HashMap<W, Vec<usize>> = HashMap::new();
+                for (v, t) in map {
+                    let targets = new_map.entry(f(v)).or_default();
+                    for x in t {
+                        if !targets.contains(&x) {
+                            targets.push(x);
+                        }
+                    }
+                }
+                new_map
+            })
+            .collect();
+
+        NFA {
+            alphabet: alphabet.into_iter().map(&f).collect(),
+            initials,
+            finals,
+            transitions,
+            epsilon,
+        }
+    }
+
+    /// Returns an NFA that accepts the words accepted by `self` from which every occurrence of `letter` has been erased.
+    pub fn project_out(mut self, letter: V) -> NFA<V> {
+        self.alphabet.remove(&letter);
+
+        // the transitions labeled by `letter` become epsilon transitions
+        for (map, eps) in self.transitions.iter_mut().zip(self.epsilon.iter_mut()) {
+            if let Some(t) = map.remove(&letter) {
+                eps.extend(t);
+            }
+        }
+
+        self.remove_epsilon()
+    }
+
+    /// Returns an NFA that accepts the words accepted by `self` which only contain letters of `keep`.
+    /// The alphabet becomes its intersection with `keep`, states left useless can be removed with `trim`.
+    pub fn restrict_alphabet(mut self, keep: &HashSet<V>) -> NFA<V> {
+        self.alphabet.retain(|x| keep.contains(x));
+        for map in &mut self.transitions {
+            map.retain(|x, _| keep.contains(x));
+        }
+
+        self
+    }
+
+    /// Returns the letters that label at least one transition.
+    pub fn used_alphabet(&self) -> HashSet<V> {
+        self.transitions
+            .iter()
+            .flat_map(|map| map.keys().cloned())
+            .collect()
+    }
+
+    /// Returns the same NFA with the letters labeling no transition removed from its alphabet.
+    pub fn shrink_alphabet(self) -> NFA<V> {
+        let used = self.used_alphabet();
+        self.restrict_alphabet(&used)
+    }
+
+    /// Returns, for each state, the minimum number of letters to read to reach it from an initial state, or `None` if it isn't reachable.
+    pub fn state_distances(&self) -> Vec<Option<usize>> {
+        let mut distances = vec![None; self.transitions.len()];
+        let mut queue = VecDeque::new();
+        for e in &self.initials {
+            distances[*e] = Some(0);
+            queue.push_back(*e);
+        }
+
+        // epsilon transitions cost nothing so their targets are visited first
+        while let Some(e) = queue.pop_front() {
+            let d = distances[e].unwrap();
+            for t in &self.epsilon[e] {
+                if distances[*t].map_or(true, |x| x > d) {
+                    distances[*t] = Some(d);
+                    queue.push_front(*t);
+                }
+            }
+            for t in self.transitions[e].values().flatten() {
+                if distances[*t].map_or(true, |x| x > d + 1) {
+                    distances[*t] = Some(d + 1);
+                    queue.push_back(*t);
+                }
+            }
+        }
+
+        distances
+    }
+
+    /// Returns `true` if `self` has the shape produced by [`kleene`](../automaton/trait.Buildable.html#tymethod.kleene), which ensures that it accepts the Kleene closure of its language:
+    /// its only initial state is final, has no incoming transition, and every final state has its transitions.
+    fn is_kleene_closed(&self) -> bool {
+        if self.initials.len() != 1 {
+            return false;
+        }
+        let i = *self.initials.iter().next().unwrap();
+
+        self.finals.contains(&i)
+            && self.epsilon[i].is_empty()
+            && self
+                .transitions
+                .iter()
+                .all(|map| map.values().all(|v| !v.contains(&i)))
+            && self.epsilon.iter().all(|e| !e.contains(&i))
+            && self.finals.iter().all(|f| {
+                self.transitions[i].iter().all(|(k, v)| {
+                    self.transitions[*f]
+                        .get(k)
+                        .map_or(false, |w| v.iter().all(|x| w.contains(x)))
+                })
+            })
+    }
+
+    /// Returns a regex accepting the same words as `self`, built by <https://en.wikipedia.org/wiki/State_elimination_method>.
+    /// The states are eliminated in increasing order of the product of their in-degree and out-degree, and the regex is simplified after each elimination.
+    pub fn to_regex_ordered(&self) -> Regex<V> {
+        let n = self.transitions.len();
+        // two extra states: a single initial state and a single final state
+        let (start, end) = (n, n + 1);
+        let mut mat: Vec<Vec<Option<Operations<V>>>> = vec![vec![None; n + 2]; n + 2];
+
+        let add = |mat: &mut Vec<Vec<Option<Operations<V>>>>, i: usize, j: usize, o| {
+            mat[i][j] = Some(match mat[i][j].take() {
+                Some(x) => x + o,
+                None => o,
+            });
+        };
+
+        for (i, m) in self.transitions.iter().enumerate() {
+            for (k, v) in m {
+                for &j in v {
+                    add(&mut mat, i, j, Operations::Letter(k.clone()));
+                }
+            }
+            for &j in &self.epsilon[i] {
+                add(&mut mat, i, j, Operations::Epsilon);
+            }
+        }
+        for &i in &self.initials {
+            add(&mut mat, start, i, Operations::Epsilon);
+        }
+        for &i in &self.finals {
+            add(&mut mat, i, end, Operations::Epsilon);
+        }
+
+        let mut remaining: BTreeSet<usize> = (0..n).collect();
+        while !remaining.is_empty() {
+            let degree = |q: usize| {
+                let ins = (0..n + 2)
+                    .filter(|&p| p != q && mat[p][q].is_some())
+                    .count();
+                let outs = (0..n + 2)
+                    .filter(|&r| r != q && mat[q][r].is_some())
+                    .count();
+                ins * outs
+            };
+            let q = *remaining.iter().min_by_key(|&&q| degree(q)).unwrap();
+            remaining.remove(&q);
+
+            let lp = mat[q][q]
+                .take()
+                .map(|x| Operations::Repeat(Box::new(x), 0, None));
+            let preds: Vec<usize> = (0..n + 2).filter(|&p| mat[p][q].is_some()).collect();
+            let succs: Vec<usize> = (0..n + 2).filter(|&r| mat[q][r].is_some()).collect();
+            for &p in &preds {
+                for &r in &succs {
+                    let mut o = mat[p][q].clone().unwrap();
+                    if let Some(lp) = &lp {
+                        o = o * lp.clone();
+                    }
+                    o = o * mat[q][r].clone().unwrap();
+                    add(&mut mat, p, r, o);
+                    mat[p][r] = mat[p][r].take().map(|x| x.simplify(&self.alphabet));
+                }
+            }
+            for p in preds {
+                mat[p][q] = None;
+            }
+            for r in succs {
+                mat[q][r] = None;
+            }
+        }
+
+        Regex {
+            alphabet: self.alphabet.clone(),
+            regex: mat[start][end]
+                .take()
+                .map_or(Operations::Empty, |x| x.simplify(&self.alphabet)),
+        }
+    }
+
+    /// Returns the sets of states reached after each prefix of `word`, starting with the empty prefix, and whether `word` is accepted.
+    /// The trace stops early if no state is reachable anymore.
+    pub fn run_trace(&self, word: &[V]) -> (Vec<HashSet<usize>>, bool) {
+        let mut actuals = self.epsilon_closure(&self.initials);
+        let mut trace = Vec::with_capacity(word.len() + 1);
+
+        for l in word {
+            if actuals.is_empty() {
+                return (trace, false);
+            }
+
+            let mut next = HashSet::new();
+            for st in &actuals {
+                if let Some(tr) = self.transitions[*st].get(l) {
+                    next.extend(tr.iter().cloned());
+                }
+            }
+            trace.push(core::mem::replace(
+                &mut actuals,
+                self.epsilon_closure(&next),
+            ));
+        }
+
+        let accepted = actuals.iter().any(|x| self.finals.contains(x));
+        if !actuals.is_empty() {
+            trace.push(actuals);
+        }
+        (trace, accepted)
+    }
+
+    /// Returns an iterator over the words accepted by `self`, in length-lexicographic order.
+    /// The iterator is lazy so it can be used on infinite languages with `take`.
+    pub fn words(&self) -> impl Iterator<Item = Vec<V>> {
+        self.to_dfa().words()
+    }
+
+    /// Returns the shortest word accepted by `self`, or `None` if `self` is [`empty`](../automaton/trait.Automata.html#empty-automaton).
+    pub fn shortest_accepted(&self) -> Option<Vec<V>> {
+        if self.has_epsilon() {
+            return self.clone().remove_epsilon().shortest_accepted();
+        }
+
+        let mut predecessors: HashMap<usize, (usize, V)> = HashMap::new();
+        let mut acc: HashSet<usize> = self.initials.clone();
+        let mut queue: VecDeque<usize> = self.initials.iter().cloned().collect();
+
+        while let Some(e) = queue.pop_front() {
+            if self.finals.contains(&e) {
+                let mut word = Vec::new();
+                let mut actual = e;
+                while let Some((prev, v)) = predecessors.get(&actual) {
+                    word.push(v.clone());
+                    actual = *prev;
+                }
+                word.reverse();
+                return Some(word);
+            }
+
+            for (v, t) in &self.transitions[e] {
+                for &x in t {
+                    if acc.insert(x) {
+                        predecessors.insert(x, (e, v.clone()));
+                        queue.push_back(x);
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Returns `true` if and only if `self` accepts a finite number of words.
+    pub fn is_finite(&self) -> bool {
+        if self.has_epsilon() {
+            return self.clone().remove_epsilon().is_finite();
+        }
+
+        // once trimmed, any cycle lies on a path from an initial state to a final state
+        let nfa = self.clone().trim();
+        let l = nfa.transitions.len();
+
+        let successors =
+            |e: usize| -> Vec<usize> { nfa.transitions[e].values().flatten().cloned().collect() };
+
+        // 0: not visited, 1: being visited, 2: visited
+        let mut state = vec![0u8; l];
+        for i in 0..l {
+            if state[i] != 0 {
+                continue;
+            }
+
+            state[i] = 1;
+            let mut stack = vec![(i, successors(i))];
+            while let Some((e, next)) = stack.last_mut() {
+                if let Some(t) = next.pop() {
+                    match state[t] {
+                        0 => {
+                            state[t] = 1;
+                            stack.push((t, successors(t)));
+                        }
+                        1 => return false,
+                        _ => {}
+                    }
+                } else {
+                    state[*e] = 2;
+                    stack.pop();
+                }
+            }
+        }
+
+        true
     }
 
     fn small_to_dfa<T: Eq + Hash + Copy + BitOr<Output = T>, C: Fn(usize) -> T>(
@@ -46,104 +749,250 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> NFA<V> {
         zero: T,
         shift: C,
     ) -> DFA<V> {
-        let mut map = HashMap::new();
-        let mut stack = VecDeque::new();
+        self.keyed_to_dfa(|set| set.iter().fold(zero, |acc, x| acc | shift(*x)))
+    }
 
-        let mut dfa = DFA::new_empty(&self.alphabet);
+    /// Determinizes `self`, the sets of states being identified by bitsets of `self.transitions.len()` bits hashed as raw words.
+    /// The successors are still computed as ordered sets, only the keys stored to recognize the sets already seen shrink.
+    #[cfg(feature = "bitvec")]
+    fn bitset_to_dfa(&self) -> DFA<V> {
+        let width = self.transitions.len();
+        self.keyed_to_dfa(|set| {
+            let mut bits: BitVec<Lsb0, u64> = BitVec::repeat(false, width);
+            for x in set {
+                bits.set(*x, true);
+            }
+            bits.into_vec()
+        })
+    }
 
-        let i: T = self.initials.iter().fold(zero, |acc, x| acc | shift(*x));
-        if self.initials.iter().any(|x| self.finals.contains(x)) {
-            dfa.finals.insert(0);
+    /// Returns a DFA equivalent to `self` like `to_dfa`, or `Err` with the number of states already created if it would need more than `max_states` states.
+    /// This bounds the memory used to determinize untrusted automatons.
+    pub fn try_to_dfa(&self, max_states: usize) -> Result<DFA<V>, usize> {
+        if self.transitions.len() < 32 {
+            self.bounded_keyed_to_dfa(
+                |set| set.iter().fold(0u32, |acc, x| acc | 1 << x),
+                max_states,
+            )
+        } else if self.transitions.len() < 64 {
+            self.bounded_keyed_to_dfa(
+                |set| set.iter().fold(0u64, |acc, x| acc | 1 << x),
+                max_states,
+            )
+        } else if self.transitions.len() < 128 {
+            self.bounded_keyed_to_dfa(
+                |set| set.iter().fold(0u128, |acc, x| acc | 1 << x),
+                max_states,
+            )
+        } else {
+            self.bounded_keyed_to_dfa(|set| set.clone(), max_states)
         }
+    }
 
-        map.insert(i, 0);
-        stack.push_back((i, HashSet::from_iter(self.initials.clone().into_iter())));
+    /// Returns a DFA equivalent to `self` built by the subset construction, with statistics about it.
+    pub fn to_dfa_with_stats(&self) -> (DFA<V>, DeterminizationStats) {
+        let max_subset_size = Cell::new(0);
+        let dfa = self.keyed_to_dfa(|set| {
+            max_subset_size.set(max_subset_size.get().max(set.len()));
+            set.clone()
+        });
+
+        let stats = DeterminizationStats {
+            subsets: dfa.transitions.len(),
+            max_subset_size: max_subset_size.get(),
+        };
+        (dfa, stats)
+    }
 
-        while let Some((elem, iter)) = stack.pop_front() {
-            let elem_num = *map.get(&elem).unwrap();
-            for v in &self.alphabet {
-                let mut it = HashSet::new();
-                for state in &iter {
-                    if let Some(transitions) = self.transitions[*state].get(&v) {
-                        for t in transitions {
-                            it.insert(*t);
-                        }
+    /// Determinizes `self`, the sets of states being identified by `key`, which must be injective.
+    fn keyed_to_dfa<T: Eq + Hash, K: Fn(&BTreeSet<usize>) -> T>(&self, key: K) -> DFA<V> {
+        self.bounded_keyed_to_dfa(key, usize::MAX)
+            .expect("a DFA can't have more than usize::MAX states")
+    }
+
+    /// Determinizes `self` like `keyed_to_dfa`, but stops and returns the number of states already created when more than `max_states` would be needed.
+    fn bounded_keyed_to_dfa<T: Eq + Hash, K: Fn(&BTreeSet<usize>) -> T>(
+        &self,
+        key: K,
+        max_states: usize,
+    ) -> Result<DFA<V>, usize> {
+        self.keyed_subset_construction(key, |set| self.subset_successors(set), max_states, |_| {})
+    }
+
+    #[cfg(any(not(feature = "bitvec"), test))]
+    fn big_to_dfa(&self) -> DFA<V> {
+        self.subset_construction(|set| self.subset_successors(set))
+    }
+
+    /// Determinizes `self` when it has too many states for the sets of states to be identified by native integers.
+    #[cfg(feature = "bitvec")]
+    fn large_to_dfa(&self) -> DFA<V> {
+        self.bitset_to_dfa()
+    }
+
+    /// Determinizes `self` when it has too many states for the sets of states to be identified by native integers.
+    #[cfg(not(feature = "bitvec"))]
+    fn large_to_dfa(&self) -> DFA<V> {
+        self.big_to_dfa()
+    }
+
+    /// Returns a DFA equivalent to `self` built by the subset construction,
+    /// and for each of its states the set of states of `self` it stands for.
+    pub fn to_dfa_labeled(&self) -> (DFA<V>, Vec<BTreeSet<usize>>) {
+        self.labeled_subset_construction(|set| self.subset_successors(set))
+    }
+
+    /// Runs `self` on the letters of `word`, which may be borrowed, so that [`run`](#method.run) doesn't clone them.
+    fn run_letters<B: Borrow<V>, I: IntoIterator<Item = B>>(&self, word: I) -> bool {
+        if self.initials.is_empty() {
+            return false;
+        }
+
+        let mut actuals = self.epsilon_closure(&self.initials);
+        let mut next = HashSet::new();
+
+        for l in word {
+            let l: &V = l.borrow();
+            for st in &actuals {
+                if let Some(tr) = self.transitions[*st].get(l) {
+                    for t in tr {
+                        next.insert(*t);
                     }
                 }
-                if it.is_empty() {
-                    continue;
-                }
+            }
 
-                let other = it.iter().fold(zero, |acc, x| acc | shift(*x));
-                let entry = map.entry(other);
-                let val = entry.or_insert_with(|| {
-                    let l = dfa.transitions.len();
-                    if it.iter().any(|x| self.finals.contains(x)) {
-                        dfa.finals.insert(l);
-                    }
-                    stack.push_back((other, it));
-                    dfa.transitions.push(HashMap::new());
-                    l
-                });
+            next = self.epsilon_closure(&next);
+            core::mem::swap(&mut actuals, &mut next);
+            if actuals.is_empty() {
+                return false;
+            }
+            next.clear();
+        }
+
+        actuals.iter().any(|x| self.finals.contains(x))
+    }
+
+    /// Returns the non-empty successors of `set` for each letter.
+    fn subset_successors(&self, set: &BTreeSet<usize>) -> Vec<(V, BTreeSet<usize>)> {
+        self.alphabet
+            .iter()
+            .filter_map(|v| self.subset_successor(set, v).map(|x| (v.clone(), x)))
+            .collect()
+    }
 
-                dfa.transitions[elem_num].insert(*v, *val);
+    /// Returns the epsilon closure of the states reachable from `set` by reading `v`, or `None` if there is none.
+    fn subset_successor(&self, set: &BTreeSet<usize>, v: &V) -> Option<BTreeSet<usize>> {
+        let mut it = HashSet::new();
+        for s in set {
+            if let Some(transitions) = self.transitions[*s].get(v) {
+                for t in transitions {
+                    it.insert(*t);
+                }
             }
         }
+        if it.is_empty() {
+            return None;
+        }
 
-        dfa
+        Some(self.epsilon_closure(&it).into_iter().collect())
     }
 
-    fn big_to_dfa(&self) -> DFA<V> {
-        let mut map: HashMap<BTreeSet<usize>, usize> = HashMap::new();
+    /// Determinizes `self` using `successors` to compute the non-empty successors of a set of states for each letter.
+    fn subset_construction<F>(&self, successors: F) -> DFA<V>
+    where
+        F: Fn(&BTreeSet<usize>) -> Vec<(V, BTreeSet<usize>)>,
+    {
+        self.keyed_subset_construction(|set| set.clone(), successors, usize::MAX, |_| {})
+            .expect("a DFA can't have more than usize::MAX states")
+    }
+
+    /// Same as `subset_construction`, but also returns the set of states of `self` each state of the DFA stands for.
+    fn labeled_subset_construction<F>(&self, successors: F) -> (DFA<V>, Vec<BTreeSet<usize>>)
+    where
+        F: Fn(&BTreeSet<usize>) -> Vec<(V, BTreeSet<usize>)>,
+    {
+        let mut labels = Vec::new();
+        let dfa = self
+            .keyed_subset_construction(
+                |set| set.clone(),
+                successors,
+                usize::MAX,
+                |set| labels.push(set.clone()),
+            )
+            .expect("a DFA can't have more than usize::MAX states");
+        (dfa, labels)
+    }
+
+    /// Determinizes `self` using `successors` to compute the non-empty successors of a set of states for each letter,
+    /// the sets of states being identified by `key`, which must be injective.
+    /// `on_state` is called on the set of states of `self` each new state of the DFA stands for, in the order of the states of the DFA.
+    /// Stops and returns the number of states already created when more than `max_states` would be needed.
+    fn keyed_subset_construction<T, K, F, S>(
+        &self,
+        key: K,
+        successors: F,
+        max_states: usize,
+        mut on_state: S,
+    ) -> Result<DFA<V>, usize>
+    where
+        T: Eq + Hash,
+        K: Fn(&BTreeSet<usize>) -> T,
+        F: Fn(&BTreeSet<usize>) -> Vec<(V, BTreeSet<usize>)>,
+        S: FnMut(&BTreeSet<usize>),
+    {
+        if max_states == 0 {
+            return Err(0);
+        }
+
+        let mut map = HashMap::new();
         let mut stack = VecDeque::new();
 
         let mut dfa = DFA::new_empty(&self.alphabet);
 
-        let initial: BTreeSet<usize> = self.initials.iter().copied().collect();
-        map.insert(initial.clone(), 0);
-        stack.push_back(initial);
-
-        if self.initials.iter().any(|x| self.finals.contains(x)) {
+        let initial: BTreeSet<usize> = self.epsilon_closure(&self.initials).into_iter().collect();
+        if initial.iter().any(|x| self.finals.contains(x)) {
             dfa.finals.insert(0);
         }
 
-        while let Some(set) = stack.pop_front() {
-            let num = *map.get(&set).unwrap();
-            for v in &self.alphabet {
-                let mut it = HashSet::new();
-                for s in &set {
-                    if let Some(transitions) = self.transitions[*s].get(&v) {
-                        for t in transitions {
-                            it.insert(*t);
+        map.insert(key(&initial), 0);
+        on_state(&initial);
+        stack.push_back((0, initial));
+
+        while let Some((num, set)) = stack.pop_front() {
+            for (v, other) in successors(&set) {
+                let k = key(&other);
+                let val = match map.get(&k) {
+                    Some(val) => *val,
+                    None => {
+                        let l = dfa.transitions.len();
+                        if l == max_states {
+                            return Err(l);
+                        }
+                        if other.iter().any(|x| self.finals.contains(x)) {
+                            dfa.finals.insert(l);
                         }
+                        map.insert(k, l);
+                        on_state(&other);
+                        stack.push_back((l, other));
+                        dfa.transitions.push(HashMap::new());
+                        l
                     }
-                }
-                if it.is_empty() {
-                    continue;
-                }
+                };
 
-                let other = it.iter().fold(BTreeSet::new(), |mut acc, x| {
-                    acc.insert(*x);
-                    acc
-                });
-                if !map.contains_key(&other) {
-                    let l = dfa.transitions.len();
-                    map.insert(other.clone(), l);
-                    if it.iter().any(|x| self.finals.contains(x)) {
-                        dfa.finals.insert(l);
-                    }
-                    stack.push_back(other.clone());
-                    dfa.transitions.push(HashMap::new());
-                }
-                dfa.transitions[num].insert(*v, *map.get(&other).unwrap());
+                dfa.transitions[num].insert(v, val);
             }
         }
 
-        dfa
+        Ok(dfa)
     }
 
     /// Returns a string containing the dot description of the automaton
     pub fn to_dot(&self) -> String {
+        self.to_dot_opts(DotOptions::default())
+    }
+
+    /// Returns a string containing the dot description of the automaton, formatted according to `opts`.
+    pub fn to_dot_opts(&self, opts: DotOptions) -> String {
         let mut ret = String::new();
         ret.push_str("digraph {");
 
@@ -166,7 +1015,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> NFA<V> {
         ret.push_str("    node [shape = circle];");
         let mut tmp_map = HashMap::new();
         for (i, map) in self.transitions.iter().enumerate() {
-            if map.is_empty() {
+            if map.is_empty() && self.epsilon[i].is_empty() {
                 ret.push_str(&format!("    S_{};", i));
             }
             for (k, v) in map {
@@ -174,14 +1023,11 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> NFA<V> {
                     tmp_map.entry(e).or_insert_with(Vec::new).push(k);
                 }
             }
+            for e in &self.epsilon[i] {
+                ret.push_str(&format!("    S_{} -> S_{} [label = \"𝜀\"];", i, e));
+            }
             for (e, v) in tmp_map.drain() {
-                let mut vs = v.into_iter().fold(String::new(), |mut acc, x| {
-                    acc.push_str(&x.to_string());
-                    acc.push_str(", ");
-                    acc
-                });
-                vs.pop();
-                vs.pop();
+                let vs = dot_label(v, opts.compact_ranges);
                 ret.push_str(&format!("    S_{} -> S_{} [label = \"{}\"];", i, e, vs));
             }
         }
@@ -202,25 +1048,28 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> NFA<V> {
             initials: HashSet::new(),
             finals: HashSet::new(),
             transitions: Vec::new(),
+            epsilon: Vec::new(),
         }
     }
 
     /// Returns a full NFA.
     pub fn new_full(alphabet: HashSet<V>) -> NFA<V> {
         NFA {
-            transitions: vec![alphabet.iter().map(|v| (*v, vec![0])).collect()],
+            transitions: vec![alphabet.iter().map(|v| (v.clone(), vec![0])).collect()],
             alphabet,
             initials: (0..=0).collect(),
             finals: (0..=0).collect(),
+            epsilon: vec![HashSet::new()],
         }
     }
 
     /// Returns a NFA that accepts all words of the given length.
+    /// With a length of 0 it is exactly [`new_empty_word`](#method.new_empty_word), which the regex compilation relies on.
     pub fn new_length(alphabet: HashSet<V>, len: usize) -> NFA<V> {
-        let mut transitions: Vec<_> = repeat(HashMap::new()).take(len).collect();
+        let mut transitions: Vec<_> = (0..len).map(|_| HashMap::new()).collect();
         for (i, map) in transitions.iter_mut().enumerate() {
             for v in &alphabet {
-                map.insert(*v, vec![i + 1]);
+                map.insert(v.clone(), vec![i + 1]);
             }
         }
 
@@ -229,26 +1078,90 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> NFA<V> {
         NFA {
             alphabet,
             initials: (0..=0).collect(),
-            finals: (len..=len).collect(),
+            finals: (len..=len).collect(),
+            transitions,
+            epsilon: (0..=len).map(|_| HashSet::new()).collect(),
+        }
+    }
+
+    /// Returns a NFA that accepts only the given word.
+    pub fn new_matching(alphabet: HashSet<V>, word: &[V]) -> NFA<V> {
+        let l = word.len();
+        let mut nfa = NFA {
+            alphabet,
+            initials: (0..=0).collect(),
+            finals: (l..=l).collect(),
+            transitions: (0..=l).map(|_| HashMap::new()).collect(),
+            epsilon: (0..=l).map(|_| HashSet::new()).collect(),
+        };
+
+        for (i, l) in word.iter().enumerate() {
+            nfa.transitions[i].insert(l.clone(), vec![i + 1]);
+        }
+
+        nfa
+    }
+
+    /// Returns a NFA that accepts exactly the given words, shaped as a prefix tree.
+    /// The letters of the words are added to the alphabet.
+    pub fn from_words(mut alphabet: HashSet<V>, words: &[Vec<V>]) -> NFA<V> {
+        if words.is_empty() {
+            return NFA::new_empty(alphabet);
+        }
+
+        // a prefix tree has at most one state per letter plus the root
+        let mut transitions: Vec<HashMap<V, Vec<usize>>> =
+            Vec::with_capacity(1 + words.iter().map(Vec::len).sum::<usize>());
+        transitions.push(HashMap::new());
+        let mut finals = HashSet::new();
+        for word in words {
+            let mut state = 0;
+            for letter in word {
+                state = match transitions[state].get(letter) {
+                    Some(next) => next[0],
+                    None => {
+                        let next = transitions.len();
+                        transitions.push(HashMap::new());
+                        transitions[state].insert(letter.clone(), vec![next]);
+                        alphabet.insert(letter.clone());
+                        next
+                    }
+                };
+            }
+            finals.insert(state);
+        }
+
+        NFA {
+            alphabet,
+            initials: (0..=0).collect(),
+            finals,
+            epsilon: (0..transitions.len()).map(|_| HashSet::new()).collect(),
             transitions,
         }
     }
 
-    /// Returns a NFA that accepts only the given word.
-    pub fn new_matching(alphabet: HashSet<V>, word: &[V]) -> NFA<V> {
-        let l = word.len();
-        let mut nfa = NFA {
-            alphabet,
-            initials: (0..=0).collect(),
-            finals: (l..=l).collect(),
-            transitions: repeat(HashMap::new()).take(l + 1).collect(),
-        };
+    /// Returns the union of the given automatons, or the empty automaton if there is none.
+    /// The states are allocated once for all of them.
+    pub fn union_all<I: IntoIterator<Item = NFA<V>>>(alphabet: HashSet<V>, items: I) -> NFA<V> {
+        let items: Vec<NFA<V>> = items.into_iter().collect();
+        let total = items.iter().map(|x| x.transitions.len()).sum();
 
-        for (i, l) in word.iter().enumerate() {
-            nfa.transitions[i].insert(*l, vec![i + 1]);
-        }
+        let mut nfa = NFA::new_empty(alphabet);
+        nfa.transitions.reserve(total);
+        nfa.epsilon.reserve(total);
+        items.into_iter().fold(nfa, |acc, x| acc.unite(x))
+    }
 
-        nfa
+    /// Returns the concatenation of the given automatons in order, or the automaton accepting only the empty word if there is none.
+    /// The states are allocated once for all of them.
+    pub fn concat_all<I: IntoIterator<Item = NFA<V>>>(alphabet: HashSet<V>, items: I) -> NFA<V> {
+        let items: Vec<NFA<V>> = items.into_iter().collect();
+        let total = items.iter().map(|x| x.transitions.len()).sum();
+
+        let mut nfa = NFA::new_empty_word(alphabet);
+        nfa.transitions.reserve(total);
+        nfa.epsilon.reserve(total);
+        items.into_iter().fold(nfa, |acc, x| acc.concatenate(x))
     }
 
     /// Returns a NFA that accepts only the empty word.
@@ -258,15 +1171,165 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> NFA<V> {
             initials: (0..=0).collect(),
             finals: (0..=0).collect(),
             transitions: vec![HashMap::new()],
+            epsilon: vec![HashSet::new()],
+        }
+    }
+
+    /// Returns the number of states of `self`.
+    pub fn num_states(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Returns the alphabet of `self`.
+    pub fn alphabet(&self) -> &HashSet<V> {
+        &self.alphabet
+    }
+
+    /// Returns the initial states of `self`.
+    pub fn initials(&self) -> &HashSet<usize> {
+        &self.initials
+    }
+
+    /// Returns the final states of `self`.
+    pub fn finals(&self) -> &HashSet<usize> {
+        &self.finals
+    }
+
+    /// Returns an iterator over the transitions of `self`, as `(from, letter, to)` triples.
+    /// The epsilon transitions are given by [`epsilon_transitions`](#method.epsilon_transitions).
+    pub fn transitions(&self) -> impl Iterator<Item = (usize, V, usize)> + '_ {
+        self.transitions.iter().enumerate().flat_map(|(from, map)| {
+            map.iter()
+                .flat_map(move |(v, t)| t.iter().map(move |to| (from, v.clone(), *to)))
+        })
+    }
+
+    /// Returns an iterator over the epsilon transitions of `self`, as `(from, to)` pairs.
+    pub fn epsilon_transitions(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.epsilon
+            .iter()
+            .enumerate()
+            .flat_map(|(from, t)| t.iter().map(move |to| (from, *to)))
+    }
+
+    /// Adds a new state without any transition and returns its index.
+    pub fn add_state(&mut self) -> usize {
+        self.transitions.push(HashMap::new());
+        self.epsilon.push(HashSet::new());
+        self.transitions.len() - 1
+    }
+
+    /// Adds a transition from `from` to `to` labeled by `letter`, adding `letter` to the alphabet if needed.
+    ///
+    /// # Panics
+    /// Panics if `from` or `to` isn't a state of `self`.
+    pub fn add_transition(&mut self, from: usize, letter: V, to: usize) {
+        let len = self.transitions.len();
+        assert!(from < len, "invalid state {}", from);
+        assert!(to < len, "invalid state {}", to);
+
+        self.alphabet.insert(letter.clone());
+        let targets = self.transitions[from].entry(letter).or_default();
+        if !targets.contains(&to) {
+            targets.push(to);
+        }
+    }
+
+    /// Adds an epsilon transition from `from` to `to`.
+    ///
+    /// # Panics
+    /// Panics if `from` or `to` isn't a state of `self`.
+    pub fn add_epsilon(&mut self, from: usize, to: usize) {
+        let len = self.transitions.len();
+        assert!(from < len, "invalid state {}", from);
+        assert!(to < len, "invalid state {}", to);
+
+        if from != to {
+            self.epsilon[from].insert(to);
+        }
+    }
+
+    /// Returns the set of states that can be reached from `states` using only epsilon transitions.
+    pub fn epsilon_closure(&self, states: &HashSet<usize>) -> HashSet<usize> {
+        let mut acc = states.clone();
+        let mut stack: Vec<usize> = states.iter().cloned().collect();
+        while let Some(e) = stack.pop() {
+            for &t in &self.epsilon[e] {
+                if acc.insert(t) {
+                    stack.push(t);
+                }
+            }
+        }
+        acc
+    }
+
+    /// Returns an equivalent NFA without any epsilon transition.
+    pub fn remove_epsilon(mut self) -> NFA<V> {
+        if !self.has_epsilon() {
+            return self;
         }
+
+        let closures: Vec<HashSet<usize>> = (0..self.transitions.len())
+            .map(|i| self.epsilon_closure(&(i..=i).collect()))
+            .collect();
+
+        let transitions = closures
+            .iter()
+            .map(|closure| {
+                let mut map: HashMap<V, Vec<usize>> = HashMap::new();
+                for e in closure {
+                    for (v, t) in &self.transitions[*e] {
+                        let targets = map.entry(v.clone()).or_default();
+                        for x in t {
+                            if !targets.contains(x) {
+                                targets.push(*x);
+                            }
+                        }
+                    }
+                }
+                map
+            })
+            .collect();
+
+        self.finals = (0..self.transitions.len())
+            .filter(|x| !closures[*x].is_disjoint(&self.finals))
+            .collect();
+        self.transitions = transitions;
+        self.epsilon = (0..self.transitions.len())
+            .map(|_| HashSet::new())
+            .collect();
+        self
+    }
+
+    fn has_epsilon(&self) -> bool {
+        self.epsilon.iter().any(|x| !x.is_empty())
+    }
+
+    /// Makes `state` an initial state.
+    ///
+    /// # Panics
+    /// Panics if `state` isn't a state of `self`.
+    pub fn add_initial(&mut self, state: usize) {
+        assert!(state < self.transitions.len(), "invalid state {}", state);
+        self.initials.insert(state);
+    }
+
+    /// Makes `state` a final state.
+    ///
+    /// # Panics
+    /// Panics if `state` isn't a state of `self`.
+    pub fn add_final(&mut self, state: usize) {
+        assert!(state < self.transitions.len(), "invalid state {}", state);
+        self.finals.insert(state);
     }
 
     /// Returns an automaton built from the raw arguments.
+    /// Repeated destinations of a state and a letter are accepted and merged, so each destination is listed once.
     pub fn from_raw(
         alphabet: HashSet<V>,
         initials: HashSet<usize>,
         finals: HashSet<usize>,
-        transitions: Vec<HashMap<V, Vec<usize>>>,
+        mut transitions: Vec<HashMap<V, Vec<usize>>>,
     ) -> Result<Self, FromRawError<V>> {
         let len = transitions.len();
 
@@ -279,27 +1342,116 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> NFA<V> {
         }
 
         for (state, map) in transitions.iter().enumerate() {
-            if let Some(&letter) = map.keys().find(|&x| !alphabet.contains(x)) {
-                return Err(FromRawError::UnknownLetter(letter));
+            if let Some(letter) = map.keys().find(|&x| !alphabet.contains(x)) {
+                return Err(FromRawError::UnknownLetter(letter.clone()));
             }
 
-            for (&letter, destinations) in map {
+            for (letter, destinations) in map {
                 if let Some(&destination) = destinations.iter().find(|&&x| x >= len) {
-                    return Err(FromRawError::InvalidTransition(state, letter, destination));
+                    return Err(FromRawError::InvalidTransition(
+                        state,
+                        letter.clone(),
+                        destination,
+                    ));
                 }
             }
         }
 
+        for destinations in transitions.iter_mut().flat_map(|map| map.values_mut()) {
+            destinations.sort_unstable();
+            destinations.dedup();
+        }
+
         Ok(NFA {
             alphabet,
             initials,
             finals,
+            epsilon: (0..len).map(|_| HashSet::new()).collect(),
             transitions,
         })
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> ToDfa<V> for NFA<V> {
+#[cfg(feature = "json")]
+impl<V: Eq + Hash + Display + Clone + Debug + Ord + Serialize> NFA<V> {
+    /// Returns the JSON representation of `self`, listing its alphabet, its initial states, its final states and the transitions of each state.
+    ///
+    /// # Panics
+    /// Panics if the letters can't be used as keys of a JSON object.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("letters can't be used as JSON keys")
+    }
+}
+
+#[cfg(feature = "json")]
+impl<V: Eq + Hash + Display + Clone + Debug + Ord + DeserializeOwned> NFA<V> {
+    /// Returns the automaton described by the given JSON, as produced by [`to_json`](#method.to_json).
+    pub fn from_json(json: &str) -> Result<NFA<V>, String> {
+        serde_json::from_str(json).map_err(|e| e.to_string())
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<V: Eq + Hash + Display + Clone + Debug + Ord + Send + Sync> NFA<V> {
+    /// Returns the same result as `to_dfa`, computing the successors for the different letters in parallel.
+    /// Only automatons with at least 128 states are determinized in parallel.
+    pub fn to_dfa_parallel(&self) -> DFA<V> {
+        if self.transitions.len() < 128 {
+            return self.to_dfa();
+        }
+
+        let letters: Vec<&V> = self.alphabet.iter().collect();
+        self.subset_construction(|set| {
+            letters
+                .par_iter()
+                .filter_map(|v| self.subset_successor(set, v).map(|x| ((*v).clone(), x)))
+                .collect()
+        })
+    }
+}
+
+/// Generates NFAs over the alphabet `{a, b, c}` with at most 8 states.
+/// Shrinking removes states and transitions.
+#[cfg(feature = "proptest")]
+impl Arbitrary for NFA<char> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<NFA<char>>;
+
+    fn arbitrary_with(_: ()) -> Self::Strategy {
+        (1..=8usize)
+            .prop_flat_map(|n| {
+                (
+                    Just(n),
+                    collection::vec(0..n, 1..=2),
+                    collection::vec(0..n, 0..=n),
+                    collection::vec((0..n, prop::char::range('a', 'c'), 0..n), 0..=3 * n),
+                    collection::vec((0..n, 0..n), 0..=n / 2),
+                )
+            })
+            .prop_map(|(n, initials, finals, transitions, epsilon)| {
+                let mut nfa = NFA::new_empty(('a'..='c').collect());
+                for _ in 0..n {
+                    nfa.add_state();
+                }
+                for state in initials {
+                    nfa.add_initial(state);
+                }
+                for state in finals {
+                    nfa.add_final(state);
+                }
+                for (from, letter, to) in transitions {
+                    nfa.add_transition(from, letter, to);
+                }
+                for (from, to) in epsilon {
+                    nfa.add_epsilon(from, to);
+                }
+                nfa
+            })
+            .boxed()
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> ToDfa<V> for NFA<V> {
     fn to_dfa(&self) -> DFA<V> {
         if self.is_empty() {
             DFA::new_empty(&self.alphabet)
@@ -310,96 +1462,37 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> ToDfa<V> for NFA<V> {
         } else if self.transitions.len() < 128 {
             self.small_to_dfa(0 as u128, |x| 1 << x)
         } else {
-            self.big_to_dfa()
+            self.large_to_dfa()
         }
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> ToNfa<V> for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> ToNfa<V> for NFA<V> {
     fn to_nfa(&self) -> NFA<V> {
         self.clone()
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> ToRegex<V> for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> ToRegex<V> for NFA<V> {
     fn to_regex(&self) -> Regex<V> {
-        let n = self.transitions.len();
-        if n == 0 {
-            return Regex {
-                alphabet: self.alphabet.clone(),
-                regex: Operations::Empty,
-            };
-        }
-
-        let mut mat1: Vec<Vec<Operations<V>>> = repeat(repeat(Operations::Empty).take(n).collect())
-            .take(n)
-            .collect();
-        let mut mat2: Vec<Vec<Operations<V>>> = mat1.clone();
-
-        for (i, m) in self.transitions.iter().enumerate() {
-            mat1[i][i] = Operations::Epsilon;
-            for (k, v) in m {
-                for &j in v {
-                    mat1[i][j] += Operations::Letter(*k);
-                }
-            }
-        }
-
-        for k in 0..n {
-            for i in 0..n {
-                for j in 0..n {
-                    mat2[i][j] = mat1[i][j].clone()
-                        + mat1[i][k].clone()
-                            * Operations::Repeat(Box::new(mat1[k][k].clone()), 0, None)
-                            * mat1[k][j].clone();
-                }
-            }
-            std::mem::swap(&mut mat1, &mut mat2);
-        }
-
-        let mut res = Operations::Empty;
-        for &st in &self.initials {
-            for &en in &self.finals {
-                res += mat1[st][en].clone();
-            }
-        }
-
-        Regex {
-            alphabet: self.alphabet.clone(),
-            regex: res,
-        }
+        self.to_regex_ordered()
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Automata<V> for NFA<V> {
     fn run(&self, v: &[V]) -> bool {
-        if self.initials.is_empty() {
-            return false;
-        }
-
-        let mut actuals = self.initials.clone();
-        let mut next = HashSet::new();
-
-        for l in v {
-            for st in &actuals {
-                if let Some(tr) = self.transitions[*st].get(l) {
-                    for t in tr {
-                        next.insert(*t);
-                    }
-                }
-            }
-
-            std::mem::swap(&mut actuals, &mut next);
-            if actuals.is_empty() {
-                return false;
-            }
-            next.clear();
-        }
+        self.run_letters(v)
+    }
 
-        actuals.iter().any(|x| self.finals.contains(x))
+    fn run_iter<I: IntoIterator<Item = V>>(&self, word: I) -> bool {
+        self.run_letters(word)
     }
 
     fn is_complete(&self) -> bool {
+        if self.has_epsilon() {
+            return self.clone().remove_epsilon().is_complete();
+        }
+
         if self.initials.is_empty() {
             return false;
         }
@@ -421,12 +1514,14 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for NFA<V>
         let mut acc: HashSet<usize> = self.initials.clone().into_iter().collect();
         let mut stack: Vec<usize> = self.initials.iter().cloned().collect();
         while let Some(e) = stack.pop() {
-            for v in self.transitions[e].values() {
-                for t in v {
-                    if !acc.contains(t) {
-                        acc.insert(*t);
-                        stack.push(*t);
-                    }
+            for t in self.transitions[e]
+                .values()
+                .flatten()
+                .chain(&self.epsilon[e])
+            {
+                if !acc.contains(t) {
+                    acc.insert(*t);
+                    stack.push(*t);
                 }
             }
         }
@@ -434,7 +1529,24 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for NFA<V>
     }
 
     fn is_coreachable(&self) -> bool {
-        self.clone().reverse().is_reachable()
+        let mut predecessors = vec![Vec::new(); self.transitions.len()];
+        for (e, (map, eps)) in self.transitions.iter().zip(&self.epsilon).enumerate() {
+            for t in map.values().flatten().chain(eps) {
+                predecessors[*t].push(e);
+            }
+        }
+
+        let mut acc: HashSet<usize> = self.finals.clone();
+        let mut stack: Vec<usize> = self.finals.iter().cloned().collect();
+        while let Some(e) = stack.pop() {
+            for t in &predecessors[e] {
+                if !acc.contains(t) {
+                    acc.insert(*t);
+                    stack.push(*t);
+                }
+            }
+        }
+        acc.len() == self.transitions.len()
     }
 
     fn is_trimmed(&self) -> bool {
@@ -450,15 +1562,17 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for NFA<V>
         let mut stack: Vec<usize> = self.initials.clone().into_iter().collect();
 
         while let Some(e) = stack.pop() {
-            for v in self.transitions[e].values() {
-                for t in v {
-                    if self.finals.contains(t) {
-                        return false;
-                    }
-                    if !acc.contains(t) {
-                        acc.insert(*t);
-                        stack.push(*t);
-                    }
+            for t in self.transitions[e]
+                .values()
+                .flatten()
+                .chain(&self.epsilon[e])
+            {
+                if self.finals.contains(t) {
+                    return false;
+                }
+                if !acc.contains(t) {
+                    acc.insert(*t);
+                    stack.push(*t);
                 }
             }
         }
@@ -466,23 +1580,29 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for NFA<V>
     }
 
     fn is_full(&self) -> bool {
-        if self.initials.is_disjoint(&self.finals) {
-            return false;
-        }
-
-        let mut acc: HashSet<usize> = self.initials.clone().into_iter().collect();
-        let mut stack: Vec<usize> = self.initials.clone().into_iter().collect();
+        // explores the subsets of states reachable by the powerset construction:
+        // `self` is full if and only if each of them, including the empty one, contains a final state
+        let start: BTreeSet<usize> = self.epsilon_closure(&self.initials).into_iter().collect();
+        let mut acc = HashSet::new();
+        let mut stack = vec![start.clone()];
+        acc.insert(start);
 
         while let Some(e) = stack.pop() {
-            for v in self.transitions[e].values() {
-                for t in v {
-                    if !self.finals.contains(t) {
-                        return false;
-                    }
-                    if !acc.contains(t) {
-                        acc.insert(*t);
-                        stack.push(*t);
-                    }
+            if e.iter().all(|x| !self.finals.contains(x)) {
+                return false;
+            }
+
+            for v in &self.alphabet {
+                let next: HashSet<usize> = e
+                    .iter()
+                    .filter_map(|x| self.transitions[*x].get(v))
+                    .flatten()
+                    .cloned()
+                    .collect();
+                let next: BTreeSet<usize> = self.epsilon_closure(&next).into_iter().collect();
+                if !acc.contains(&next) {
+                    acc.insert(next.clone());
+                    stack.push(next);
                 }
             }
         }
@@ -498,11 +1618,10 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for NFA<V>
             return self;
         }
 
-        let l = self.transitions.len();
-        self.transitions.push(HashMap::new());
+        let l = self.add_state();
         for m in &mut self.transitions {
             for v in &self.alphabet {
-                let t = m.entry(*v).or_insert_with(Vec::new);
+                let t = m.entry(v.clone()).or_insert_with(Vec::new);
                 if t.is_empty() {
                     t.push(l);
                 }
@@ -520,12 +1639,14 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for NFA<V>
         let mut acc: HashSet<usize> = self.initials.clone().into_iter().collect();
         let mut stack: Vec<usize> = self.initials.iter().cloned().collect();
         while let Some(e) = stack.pop() {
-            for v in self.transitions[e].values() {
-                for t in v {
-                    if !acc.contains(t) {
-                        acc.insert(*t);
-                        stack.push(*t);
-                    }
+            for t in self.transitions[e]
+                .values()
+                .flatten()
+                .chain(&self.epsilon[e])
+            {
+                if !acc.contains(t) {
+                    acc.insert(*t);
+                    stack.push(*t);
                 }
             }
         }
@@ -537,10 +1658,12 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for NFA<V>
             if acc.contains(&i) {
                 map.insert(i, ind);
                 self.transitions.swap(i, ind);
+                self.epsilon.swap(i, ind);
                 ind += 1;
             }
         }
         self.transitions.truncate(ind);
+        self.epsilon.truncate(ind);
 
         self.finals = self
             .finals
@@ -557,6 +1680,9 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for NFA<V>
                 }
             }
         }
+        for eps in &mut self.epsilon {
+            *eps = eps.iter().map(|t| *map.get(t).unwrap()).collect();
+        }
 
         self
     }
@@ -566,88 +1692,116 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Automata<V> for NFA<V>
     }
 
     fn trim(self) -> NFA<V> {
+        // both checks are cheaper than rebuilding the transitions
+        if self.is_trimmed() {
+            return self;
+        }
+
         self.make_reachable().make_coreachable()
     }
 
     fn reverse(mut self) -> NFA<V> {
-        let mut transitions: Vec<_> = repeat(HashMap::new())
-            .take(self.transitions.len())
+        let mut transitions: Vec<HashMap<V, Vec<usize>>> = (0..self.transitions.len())
+            .map(|_| HashMap::new())
             .collect();
 
         for i in 0..self.transitions.len() {
             for (k, v) in &self.transitions[i] {
                 for e in v {
-                    transitions[*e].entry(*k).or_insert_with(Vec::new).push(i);
+                    transitions[*e].entry(k.clone()).or_default().push(i);
                 }
             }
         }
 
+        let mut epsilon: Vec<HashSet<usize>> =
+            (0..self.epsilon.len()).map(|_| HashSet::new()).collect();
+        for (i, eps) in self.epsilon.iter().enumerate() {
+            for e in eps {
+                epsilon[*e].insert(i);
+            }
+        }
+
         self.transitions = transitions;
-        std::mem::swap(&mut self.initials, &mut self.finals);
+        self.epsilon = epsilon;
+        core::mem::swap(&mut self.initials, &mut self.finals);
         self
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Buildable<V> for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Buildable<V> for NFA<V> {
     fn unite(mut self, other: NFA<V>) -> NFA<V> {
         let NFA {
             alphabet,
             initials,
             finals,
             transitions,
+            epsilon,
         } = other;
 
         let l = self.transitions.len();
+        self.transitions.reserve(transitions.len());
+        self.epsilon.reserve(epsilon.len());
 
         append_hashset(&mut self.alphabet, alphabet);
         append_shift_hashset(&mut self.initials, initials, l);
         append_shift_hashset(&mut self.finals, finals, l);
         append_shift_transitions(&mut self.transitions, transitions);
+        append_shift_epsilon(&mut self.epsilon, epsilon);
 
         self
     }
 
     fn concatenate(mut self, mut other: NFA<V>) -> NFA<V> {
         let l = self.transitions.len();
+        let mut starts = other.epsilon_closure(&other.initials);
+        shift_hashset(&mut starts, l);
         shift_fnda(&mut other, l);
         let NFA {
             alphabet,
-            initials,
             finals,
             mut transitions,
+            mut epsilon,
+            ..
         } = other;
 
         append_hashset(&mut self.alphabet, alphabet);
+        self.transitions.reserve(transitions.len());
+        self.epsilon.reserve(epsilon.len());
 
-        for e in &initials {
+        for e in &starts {
             for (v, t) in &mut transitions[e - l] {
                 // e - l because of the shift above
                 for f in &self.finals {
                     self.transitions[*f]
-                        .entry(*v)
+                        .entry(v.clone())
                         .or_insert_with(Vec::new)
                         .append(&mut t.clone());
                 }
             }
         }
 
-        if finals.is_disjoint(&initials) {
+        if finals.is_disjoint(&starts) {
             self.finals = finals;
         } else {
             append_hashset(&mut self.finals, finals);
         }
         self.transitions.append(&mut transitions);
+        self.epsilon.append(&mut epsilon);
 
         self
     }
 
     fn kleene(mut self) -> NFA<V> {
+        if self.is_kleene_closed() {
+            return self;
+        }
+
         let l = self.transitions.len();
         let mut map = HashMap::new();
 
-        for i in &self.initials {
+        for i in &self.epsilon_closure(&self.initials) {
             for (k, v) in &self.transitions[*i] {
-                let set = &mut map.entry(*k).or_insert_with(HashSet::new);
+                let set = &mut map.entry(k.clone()).or_insert_with(HashSet::new);
                 for x in v {
                     set.insert(*x);
                 }
@@ -657,14 +1811,14 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Buildable<V> for NFA<V
         for i in &self.finals {
             for (k, v) in &map {
                 let mut set: HashSet<usize> = self.transitions[*i]
-                    .entry(*k)
+                    .entry(k.clone())
                     .or_insert_with(Vec::new)
                     .drain(..)
                     .collect();
                 for x in v {
                     set.insert(*x);
                 }
-                self.transitions[*i].insert(*k, set.into_iter().collect());
+                self.transitions[*i].insert(k.clone(), set.into_iter().collect());
             }
         }
 
@@ -673,6 +1827,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Buildable<V> for NFA<V
                 .map(|(k, v)| (k, v.into_iter().collect()))
                 .collect(),
         );
+        self.epsilon.push(HashSet::new());
         self.initials.clear();
         self.initials.insert(l);
         self.finals.insert(l);
@@ -682,10 +1837,9 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Buildable<V> for NFA<V
 
     fn at_most(mut self, u: usize) -> NFA<V> {
         if !self.initials.iter().any(|x| self.finals.contains(x)) {
-            let l = self.transitions.len();
+            let l = self.add_state();
             self.initials.insert(l);
             self.finals.insert(l);
-            self.transitions.push(HashMap::new());
         }
 
         (0..u).fold(NFA::new_empty_word(self.alphabet.clone()), |acc, _| {
@@ -702,14 +1856,17 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Buildable<V> for NFA<V
     }
 
     fn repeat<R: RangeBounds<usize>>(self, r: R) -> NFA<V> {
+        // an excluded bound of usize::MAX or 0 leaves no possible number of repetitions
         let start = match r.start_bound() {
             Included(&a) => a,
+            Excluded(&usize::MAX) => return NFA::new_empty(self.alphabet),
             Excluded(&a) => a + 1,
             Unbounded => 0,
         };
 
         let end = match r.end_bound() {
             Included(&a) => Some(a),
+            Excluded(&0) => return NFA::new_empty(self.alphabet),
             Excluded(&a) => Some(a - 1),
             Unbounded => None,
         };
@@ -732,25 +1889,25 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Buildable<V> for NFA<V
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<NFA<V>> for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<NFA<V>> for NFA<V> {
     fn eq(&self, other: &NFA<V>) -> bool {
         self.le(other) && self.ge(other)
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<DFA<V>> for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<DFA<V>> for NFA<V> {
     fn eq(&self, other: &DFA<V>) -> bool {
         self.eq(&other.to_nfa())
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<Regex<V>> for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<Regex<V>> for NFA<V> {
     fn eq(&self, other: &Regex<V>) -> bool {
         self.eq(&other.to_nfa())
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<Automaton<V>> for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<Automaton<V>> for NFA<V> {
     fn eq(&self, other: &Automaton<V>) -> bool {
         match other {
             Automaton::DFA(v) => self.eq(&*v),
@@ -760,7 +1917,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<Automaton<V>
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialOrd for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialOrd for NFA<V> {
     fn partial_cmp(&self, other: &NFA<V>) -> Option<Ordering> {
         match (self.ge(&other), self.le(&other)) {
             (true, true) => Some(Equal),
@@ -787,16 +1944,194 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialOrd for NFA<V>
     }
 }
 
+impl NFA<char> {
+    /// Returns `true` if and only if `self` accepts the characters of `s`.
+    pub fn run_str(&self, s: &str) -> bool {
+        self.run(&s.chars().collect::<Vec<_>>())
+    }
+
+    /// An alias for [`run_str`](#method.run_str).
+    pub fn is_match(&self, s: &str) -> bool {
+        self.run_str(s)
+    }
+
+    /// Returns the automaton described by the given dot, which must use the same subset of dot as [`to_dot`](#method.to_dot) or [`DFA::to_dot`](../dfa/struct.DFA.html#method.to_dot).
+    pub fn from_dot(src: &str) -> Result<NFA<char>, String> {
+        let body = src
+            .trim()
+            .strip_prefix("digraph")
+            .map(str::trim_start)
+            .and_then(|x| x.strip_prefix('{'))
+            .and_then(|x| x.trim_end().strip_suffix('}'))
+            .ok_or_else(|| "Expected digraph { ... }".to_string())?;
+
+        // the statements are separated by semicolons that aren't in a label
+        let mut statements = Vec::new();
+        let mut start = 0;
+        let mut quoted = false;
+        for (i, c) in body.char_indices() {
+            if c == '"' {
+                quoted = !quoted;
+            } else if c == ';' && !quoted {
+                statements.push(body[start..i].trim());
+                start = i + 1;
+            }
+        }
+        if quoted {
+            return Err("Unterminated label".to_string());
+        }
+        statements.push(body[start..].trim());
+
+        let index = |name: &str, prefix: &str| -> Result<usize, String> {
+            name.strip_prefix(prefix)
+                .and_then(|x| x.parse::<usize>().ok())
+                .ok_or_else(|| format!("Invalid node {}", name))
+        };
+
+        let mut nfa = NFA::new_empty(HashSet::new());
+        let mut shape = "circle";
+        for statement in statements.into_iter().filter(|x| !x.is_empty()) {
+            if let Some(attributes) = statement.strip_prefix("node") {
+                shape = match attributes.replace(' ', "").as_str() {
+                    "[shape=doublecircle]" => "doublecircle",
+                    "[shape=point]" => "point",
+                    "[shape=circle]"
+                    | "[shape=circle,style=solid]"
+                    | "[shape=circle,style=dashed]" => "circle",
+                    _ => return Err(format!("Invalid statement {}", statement)),
+                };
+            } else if let Some(arrow) = statement.find("->") {
+                let from = statement[..arrow].trim();
+                let rest = statement[arrow + 2..].trim_start();
+                let (to, attributes) = match rest.find('[') {
+                    Some(i) => (rest[..i].trim(), Some(&rest[i..])),
+                    None => (rest.trim(), None),
+                };
+
+                let to = index(to, "S_")?;
+                while nfa.transitions.len() <= to {
+                    nfa.add_state();
+                }
+
+                if from.starts_with("I_") {
+                    if index(from, "I_")? != to || attributes.is_some() {
+                        return Err(format!("Invalid statement {}", statement));
+                    }
+                    nfa.add_initial(to);
+                    continue;
+                }
+
+                let from = index(from, "S_")?;
+                while nfa.transitions.len() <= from {
+                    nfa.add_state();
+                }
+
+                let label = attributes
+                    .and_then(|x| x.strip_prefix('['))
+                    .and_then(|x| x.trim_end().strip_suffix(']'))
+                    .and_then(|x| x.trim().strip_prefix("label"))
+                    .and_then(|x| x.trim_start().strip_prefix('='))
+                    .and_then(|x| x.trim().strip_prefix('"'))
+                    .and_then(|x| x.strip_suffix('"'))
+                    .ok_or_else(|| format!("Invalid statement {}", statement))?;
+
+                for letter in label.split(", ") {
+                    let mut chars = letter.chars();
+                    match (chars.next(), chars.next(), chars.next(), chars.next()) {
+                        (Some('𝜀'), None, _, _) => nfa.add_epsilon(from, to),
+                        (Some(c), None, _, _) => nfa.add_transition(from, c, to),
+                        (Some(a), Some('-'), Some(b), None) if a < b => {
+                            for c in (a as u32..=b as u32).filter_map(core::char::from_u32) {
+                                nfa.add_transition(from, c, to);
+                            }
+                        }
+                        _ => return Err(format!("Invalid label {}", label)),
+                    }
+                }
+            } else {
+                for name in statement.split_whitespace() {
+                    if shape == "point" {
+                        index(name, "I_")?;
+                        continue;
+                    }
+
+                    let state = index(name, "S_")?;
+                    while nfa.transitions.len() <= state {
+                        nfa.add_state();
+                    }
+                    if shape == "doublecircle" {
+                        nfa.add_final(state);
+                    }
+                }
+            }
+        }
+
+        Ok(nfa)
+    }
+}
+
+/// Displays the transition table of the automaton, with a row per state and a column per letter, plus a column for epsilon transitions if there are any.
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Display for NFA<V> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut alphabet: Vec<_> = self.alphabet.iter().collect();
+        alphabet.sort();
+
+        let targets = |mut v: Vec<usize>| {
+            v.sort();
+            v.iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        let mut header: Vec<String> = alphabet.iter().map(|x| x.to_string()).collect();
+        if self.has_epsilon() {
+            header.push("𝜀".to_string());
+        }
+        let rows = self
+            .transitions
+            .iter()
+            .enumerate()
+            .map(|(i, map)| {
+                let mut cells: Vec<String> = alphabet
+                    .iter()
+                    .map(|v| targets(map.get(v).cloned().unwrap_or_default()))
+                    .collect();
+                if self.has_epsilon() {
+                    cells.push(targets(self.epsilon[i].iter().cloned().collect()));
+                }
+                (
+                    i,
+                    self.initials.contains(&i),
+                    self.finals.contains(&i),
+                    cells,
+                )
+            })
+            .collect();
+
+        write_table(f, header, rows)
+    }
+}
+
 impl FromStr for NFA<char> {
-    type Err = String;
+    type Err = ParseError;
 
     fn from_str(s: &str) -> Result<NFA<char>, Self::Err> {
         s.parse::<Regex<char>>().map(|x| x.to_nfa())
     }
 }
 
+/// Collecting words gives NFA::from_words over the letters they contain.
+/// The result is nondeterministic until converted with `to_dfa`.
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> FromIterator<Vec<V>> for NFA<V> {
+    fn from_iter<I: IntoIterator<Item = Vec<V>>>(iter: I) -> NFA<V> {
+        let words: Vec<Vec<V>> = iter.into_iter().collect();
+        NFA::from_words(HashSet::new(), &words)
+    }
+}
+
 /// The multiplication of A and B is A.concatenate(B)
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Mul for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Mul for NFA<V> {
     type Output = Self;
 
     fn mul(self, other: NFA<V>) -> NFA<V> {
@@ -805,7 +2140,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Mul for NFA<V> {
 }
 
 /// The negation of A is A.negate().
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Neg for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Neg for NFA<V> {
     type Output = Self;
 
     fn neg(self) -> NFA<V> {
@@ -814,7 +2149,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Neg for NFA<V> {
 }
 
 /// The opposite of A is A.reverse().
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Not for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Not for NFA<V> {
     type Output = Self;
 
     fn not(self) -> NFA<V> {
@@ -823,19 +2158,97 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Not for NFA<V> {
 }
 
 /// The substraction of A and B is an automaton that accepts a word if and only if A accepts it and B doesn't.
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Sub for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Sub for NFA<V> {
     type Output = Self;
 
     fn sub(self, other: NFA<V>) -> NFA<V> {
-        self.intersect(other.negate())
+        self.difference(other)
     }
 }
 
 /// The addition fo A and B is an automaton that accepts a word if and only if A or B accept it.
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Add for NFA<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Add for NFA<V> {
     type Output = Self;
 
     fn add(self, other: NFA<V>) -> NFA<V> {
         self.unite(other)
     }
 }
+
+/// The bitwise and of A and B is A.intersect(B).
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> BitAnd for NFA<V> {
+    type Output = Self;
+
+    fn bitand(self, other: NFA<V>) -> NFA<V> {
+        self.intersect(other)
+    }
+}
+
+/// The bitwise or of A and B is A.unite(B), like their addition.
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> BitOr for NFA<V> {
+    type Output = Self;
+
+    fn bitor(self, other: NFA<V>) -> NFA<V> {
+        self.unite(other)
+    }
+}
+
+/// Returns the comma separated letters of a dot label, with runs of at least three consecutive characters collapsed if `compact`.
+fn dot_label<V: Display>(letters: Vec<&V>, compact: bool) -> String {
+    let strings: Vec<String> = letters.into_iter().map(|x| x.to_string()).collect();
+
+    // ranges only make sense if every letter is displayed as a single character
+    let mut chars = Vec::new();
+    for x in &strings {
+        let mut it = x.chars();
+        match (it.next(), it.next()) {
+            (Some(c), None) if compact => chars.push(c),
+            _ => return strings.join(", "),
+        }
+    }
+    chars.sort();
+
+    let mut parts = Vec::new();
+    let mut i = 0;
+    while i < chars.len() {
+        let mut j = i;
+        while j + 1 < chars.len() && chars[j + 1] as u32 == chars[j] as u32 + 1 {
+            j += 1;
+        }
+        if j >= i + 2 {
+            parts.push(format!("{}-{}", chars[i], chars[j]));
+        } else {
+            parts.extend(chars[i..=j].iter().map(|x| x.to_string()));
+        }
+        i = j + 1;
+    }
+
+    parts.join(", ")
+}
+
+// `bitset_to_dfa` and `big_to_dfa` are private, so they can't be compared from the integration tests.
+#[cfg(all(test, feature = "bitvec"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bitset_to_dfa() {
+        // automaton7 of the integration tests, scaled up to at least 128 states
+        let aut = Regex::parse_with_alphabet(
+            (b'0'..=b'9').map(char::from).collect(),
+            "0(8+4*3*)*|86+(3+|578)((3*|4?6?)+|(4*|86+|2)37*|54|.|5*)|.8*|(3*0*)+|2*|7*2|.3|3*5*|(50|7)1|21|4+|(30*|6|9*2*)*|1+(608*)*",
+        )
+        .unwrap()
+        .to_nfa();
+        let mut scaled = aut.clone();
+        while scaled.transitions.len() < 128 {
+            scaled = scaled.unite(aut.clone());
+        }
+
+        let bitset = scaled.bitset_to_dfa();
+        let big = scaled.big_to_dfa();
+        assert_eq!(bitset.initial, big.initial);
+        assert_eq!(bitset.finals, big.finals);
+        assert_eq!(bitset.transitions, big.transitions);
+    }
+}
@@ -1,6 +1,9 @@
-use crate::{parser::Token::*, regex::Operations};
+use crate::{
+    compat::*,
+    parser::Token::*,
+    regex::{Operations, ParseError},
+};
 use logos::Logos;
-use std::collections::{BTreeSet, VecDeque};
 
 /// The token used by [`logos`](/logos/index.html`]).
 #[derive(Logos, Debug, PartialEq, Clone)]
@@ -14,6 +17,12 @@ pub enum Token {
     #[token = "|"]
     Union,
 
+    #[token = "&"]
+    Inter,
+
+    #[token = "~"]
+    Tilde,
+
     #[token = "("]
     Lpar,
 
@@ -35,45 +44,106 @@ pub enum Token {
     #[token = "𝜀"]
     Epsilon,
 
-    #[regex = "[^|+().*?𝜀]"]
+    #[token = "∅"]
+    EmptySet,
+
+    #[token = "{"]
+    Lbrace,
+
+    #[token = "}"]
+    Rbrace,
+
+    #[token = "["]
+    Lbracket,
+
+    #[token = "]"]
+    Rbracket,
+
+    #[regex = "\\\\."]
+    Escaped,
+
+    #[regex = "[^|&~+().*?𝜀∅{}\\[\\]\\\\]"]
     Letter,
 }
 
 /*
-    (REG) > REG* = REG+ = REG? > REGREG > REG|REG
+    (REG) > REG* = REG+ = REG? > ~REG > REGREG > REG&REG > REG|REG
 
     REG ::= .
-            𝜀
+            𝜀 or \e
+            ∅ or \0
             CHAR
+            [CLASS]
             (REG)
             REG*
             REG+
             REG?
+            REG{n}
+            REG{n,}
+            REG{,m}
+            REG{n,m}
+            ~REG
             REGREG
+            REG&REG
             REG|REG
+
+    CLASS ::= ^CLASS
+              CHAR
+              CHAR-CHAR
+              CLASSCLASS
+
+    CHAR ::= any character other than |&~+().*?𝜀∅{}[]\
+             \ANY other than \e and \0
 */
 
-pub(crate) fn tokens(s: &str) -> VecDeque<(Token, &str)> {
+pub(crate) fn tokens(s: &str) -> VecDeque<(Token, &str, usize)> {
     let mut lexer = Token::lexer(s);
     let mut tokens = VecDeque::new();
 
     while lexer.token != Token::End {
-        tokens.push_back((lexer.token.clone(), lexer.slice()));
+        // an escaped character is a letter, the backslash is removed by `letter`,
+        // except for the ASCII forms of the empty word and of the empty set
+        let token = match lexer.token {
+            Escaped if lexer.slice() == "\\e" => Epsilon,
+            Escaped if lexer.slice() == "\\0" => EmptySet,
+            Escaped => Letter,
+            ref x => x.clone(),
+        };
+        tokens.push_back((token, lexer.slice(), lexer.range().start));
         lexer.advance();
     }
+    tokens.push_back((End, "", s.len()));
 
     tokens
 }
 
-pub(crate) fn peak(tokens: &mut VecDeque<(Token, &str)>) -> Option<Token> {
+pub(crate) fn letter(s: &str) -> char {
+    let mut chars = s.chars();
+    match chars.next() {
+        Some('\\') => chars.next().unwrap(),
+        x => x.unwrap(),
+    }
+}
+
+pub(crate) fn error(tokens: &VecDeque<(Token, &str, usize)>, message: String) -> ParseError {
+    ParseError {
+        message,
+        position: tokens[0].2,
+    }
+}
+
+pub(crate) fn peak(tokens: &VecDeque<(Token, &str, usize)>) -> Option<Token> {
     tokens.get(0).map(|x| x.0.clone())
 }
 
-pub(crate) fn read_union(tokens: &mut VecDeque<(Token, &str)>) -> Result<Operations<char>, String> {
+pub(crate) fn read_union(
+    tokens: &mut VecDeque<(Token, &str, usize)>,
+    alphabet: Option<&HashSet<char>>,
+) -> Result<Operations<char>, ParseError> {
     let mut u = BTreeSet::new();
 
     loop {
-        u.insert(read_concat(tokens)?);
+        u.insert(read_inter(tokens, alphabet)?);
         if peak(tokens) == Some(Union) {
             tokens.pop_front();
         } else {
@@ -89,25 +159,209 @@ pub(crate) fn read_union(tokens: &mut VecDeque<(Token, &str)>) -> Result<Operati
     }
 }
 
-pub(crate) fn read_paren(tokens: &mut VecDeque<(Token, &str)>) -> Result<Operations<char>, String> {
+pub(crate) fn read_inter(
+    tokens: &mut VecDeque<(Token, &str, usize)>,
+    alphabet: Option<&HashSet<char>>,
+) -> Result<Operations<char>, ParseError> {
+    let mut u = BTreeSet::new();
+
+    loop {
+        u.insert(read_concat(tokens, alphabet)?);
+        if peak(tokens) == Some(Inter) {
+            tokens.pop_front();
+        } else {
+            break;
+        }
+    }
+
+    if u.len() == 1 {
+        Ok(u.into_iter().next().unwrap())
+    } else {
+        Ok(Operations::Intersect(u))
+    }
+}
+
+pub(crate) fn read_complement(
+    tokens: &mut VecDeque<(Token, &str, usize)>,
+    alphabet: Option<&HashSet<char>>,
+) -> Result<Operations<char>, ParseError> {
+    if peak(tokens) != Some(Tilde) {
+        return Err(error(tokens, "Expected tilde.".to_string()));
+    }
+    tokens.pop_front();
+
+    let o = match peak(tokens) {
+        Some(Tilde) => read_complement(tokens, alphabet)?,
+        Some(Lpar) => read_paren(tokens, alphabet)?,
+        Some(Lbracket) => read_class(tokens, alphabet)?,
+        _ => read_letter(tokens)?,
+    };
+    Ok(Operations::Complement(Box::new(o)))
+}
+
+pub(crate) fn read_paren(
+    tokens: &mut VecDeque<(Token, &str, usize)>,
+    alphabet: Option<&HashSet<char>>,
+) -> Result<Operations<char>, ParseError> {
     if peak(tokens) != Some(Lpar) {
-        return Err("Expected left parenthesis.".to_string());
+        return Err(error(tokens, "Expected left parenthesis.".to_string()));
     }
     tokens.pop_front();
 
-    let o = read_union(tokens)?;
+    let o = read_union(tokens, alphabet)?;
 
     if peak(tokens) != Some(Rpar) {
-        return Err("Expected right parenthesis.".to_string());
+        return Err(error(tokens, "Expected right parenthesis.".to_string()));
+    }
+    tokens.pop_front();
+    read_quantif(tokens, o)
+}
+
+pub(crate) fn read_class(
+    tokens: &mut VecDeque<(Token, &str, usize)>,
+    alphabet: Option<&HashSet<char>>,
+) -> Result<Operations<char>, ParseError> {
+    if peak(tokens) != Some(Lbracket) {
+        return Err(error(tokens, "Expected left bracket.".to_string()));
+    }
+    let start = tokens[0].2;
+    tokens.pop_front();
+
+    let negated = peak(tokens) == Some(Letter) && tokens[0].1 == "^";
+    if negated {
+        tokens.pop_front();
+    }
+
+    let mut letters = BTreeSet::new();
+    while peak(tokens) == Some(Letter) {
+        let first = letter(tokens[0].1);
+        let position = tokens[0].2;
+        tokens.pop_front();
+
+        let is_range = peak(tokens) == Some(Letter)
+            && tokens[0].1 == "-"
+            && tokens.get(1).map(|x| x.0.clone()) == Some(Letter);
+        if is_range {
+            let last = letter(tokens[1].1);
+            tokens.pop_front();
+            tokens.pop_front();
+            if last < first {
+                return Err(ParseError {
+                    message: format!("Invalid range {}-{}", first, last),
+                    position,
+                });
+            }
+            letters.extend(
+                (first as u32..=last as u32)
+                    .filter_map(core::char::from_u32)
+                    .map(Operations::Letter),
+            );
+        } else {
+            letters.insert(Operations::Letter(first));
+        }
+    }
+
+    if peak(tokens) != Some(Rbracket) {
+        return Err(error(tokens, "Expected right bracket.".to_string()));
     }
     tokens.pop_front();
-    Ok(read_quantif(tokens, o))
+
+    if letters.is_empty() {
+        return Err(ParseError {
+            message: "Empty character class".to_string(),
+            position: start,
+        });
+    }
+
+    if negated {
+        let alphabet = alphabet.ok_or_else(|| ParseError {
+            message: "Negated character classes need a known alphabet, use parse_with_alphabet"
+                .to_string(),
+            position: start,
+        })?;
+        letters = alphabet
+            .iter()
+            .map(|x| Operations::Letter(*x))
+            .filter(|x| !letters.contains(x))
+            .collect();
+        if letters.is_empty() {
+            return read_quantif(tokens, Operations::Empty);
+        }
+    }
+
+    let o = if letters.len() == 1 {
+        letters.into_iter().next().unwrap()
+    } else {
+        Operations::Union(letters)
+    };
+    read_quantif(tokens, o)
+}
+
+pub(crate) fn read_bounds(
+    tokens: &mut VecDeque<(Token, &str, usize)>,
+) -> Result<(usize, Option<usize>), ParseError> {
+    if peak(tokens) != Some(Lbrace) {
+        return Err(error(tokens, "Expected left brace.".to_string()));
+    }
+    let start = tokens[0].2;
+    tokens.pop_front();
+
+    let mut content = String::new();
+    while let Some(x) = peak(tokens) {
+        if x == Rbrace {
+            break;
+        } else if x != Letter {
+            return Err(error(tokens, "Expected right brace.".to_string()));
+        }
+        content.push_str(tokens[0].1);
+        tokens.pop_front();
+    }
+
+    if peak(tokens) != Some(Rbrace) {
+        return Err(error(tokens, "Expected right brace.".to_string()));
+    }
+    tokens.pop_front();
+
+    let number = |s: &str| {
+        s.parse::<usize>().map_err(|_| ParseError {
+            message: format!("Invalid repetition bound {}", s),
+            position: start,
+        })
+    };
+
+    let (min, max) = match content.find(',') {
+        None => {
+            let n = number(&content)?;
+            (n, Some(n))
+        }
+        Some(i) => {
+            let (min, max) = (&content[..i], &content[i + 1..]);
+            let min = if min.is_empty() { 0 } else { number(min)? };
+            let max = if max.is_empty() {
+                None
+            } else {
+                Some(number(max)?)
+            };
+            (min, max)
+        }
+    };
+
+    if let Some(max) = max {
+        if max < min {
+            return Err(ParseError {
+                message: format!("Invalid repetition {{{}}}", content),
+                position: start,
+            });
+        }
+    }
+
+    Ok((min, max))
 }
 
 pub(crate) fn read_quantif(
-    tokens: &mut VecDeque<(Token, &str)>,
+    tokens: &mut VecDeque<(Token, &str, usize)>,
     mut o: Operations<char>,
-) -> Operations<char> {
+) -> Result<Operations<char>, ParseError> {
     while let Some(x) = peak(tokens) {
         if x == Plus {
             o = Operations::Repeat(Box::new(o), 1, None);
@@ -115,50 +369,68 @@ pub(crate) fn read_quantif(
             o = Operations::Repeat(Box::new(o), 0, None);
         } else if x == Question {
             o = Operations::Repeat(Box::new(o), 0, Some(1));
+        } else if x == Lbrace {
+            let (min, max) = read_bounds(tokens)?;
+            o = Operations::Repeat(Box::new(o), min, max);
+            continue;
         } else {
             break;
         }
         tokens.pop_front();
     }
 
-    o
+    Ok(o)
 }
 
 pub(crate) fn read_letter(
-    tokens: &mut VecDeque<(Token, &str)>,
-) -> Result<Operations<char>, String> {
+    tokens: &mut VecDeque<(Token, &str, usize)>,
+) -> Result<Operations<char>, ParseError> {
     if let Some(x) = peak(tokens) {
         let o = if x == Dot {
             Operations::Dot
         } else if x == Epsilon {
             Operations::Epsilon
+        } else if x == EmptySet {
+            Operations::Empty
         } else if x == Letter {
-            Operations::Letter(tokens[0].1.chars().next().unwrap())
+            Operations::Letter(letter(tokens[0].1))
         } else {
-            return Err("Expected letter".to_string());
+            return Err(error(tokens, "Expected letter".to_string()));
         };
         tokens.pop_front();
-        Ok(read_quantif(tokens, o))
+        read_quantif(tokens, o)
     } else {
-        Err("Expected letter".to_string())
+        Err(error(tokens, "Expected letter".to_string()))
     }
 }
 
 pub(crate) fn read_concat(
-    tokens: &mut VecDeque<(Token, &str)>,
-) -> Result<Operations<char>, String> {
+    tokens: &mut VecDeque<(Token, &str, usize)>,
+    alphabet: Option<&HashSet<char>>,
+) -> Result<Operations<char>, ParseError> {
     let mut c = VecDeque::new();
     while let Some(x) = peak(tokens) {
-        if x == Dot || x == Epsilon || x == Letter {
+        if x == Dot || x == Epsilon || x == EmptySet || x == Letter {
             c.push_back(read_letter(tokens)?);
         } else if x == Lpar {
-            c.push_back(read_paren(tokens)?);
-        } else if x == Kleene || x == Plus || x == Question {
-            return Err(format!(
-                "Unexpected {}",
-                tokens[0].1.chars().next().unwrap()
+            c.push_back(read_paren(tokens, alphabet)?);
+        } else if x == Lbracket {
+            c.push_back(read_class(tokens, alphabet)?);
+        } else if x == Tilde {
+            c.push_back(read_complement(tokens, alphabet)?);
+        } else if x == Kleene
+            || x == Plus
+            || x == Question
+            || x == Lbrace
+            || x == Rbrace
+            || x == Rbracket
+            || x == Error
+        {
+            return Err(error(
+                tokens,
+                format!("Unexpected {}", tokens[0].1.chars().next().unwrap()),
             ));
-        } else if x == Rpar || x == Union || x == End {
+        } else if x == Rpar || x == Union || x == Inter || x == End {
             break;
         } else {
             unreachable!()
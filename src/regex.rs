@@ -1,31 +1,47 @@
 use crate::{
-    automaton::{Automaton, Buildable},
+    automaton::{Automata, Automaton, Buildable},
+    compat::*,
     dfa::{ToDfa, DFA},
     nfa::{ToNfa, NFA},
     parser::*,
     utils::*,
 };
-use std::{
+use core::{
     cmp::{Ordering, Ordering::*},
-    collections::{BTreeSet, HashSet, VecDeque},
-    fmt::{Debug, Display},
+    fmt::{self, Debug, Display, Formatter},
     hash::Hash,
     ops::{Add, AddAssign, Bound::*, Mul, RangeBounds},
     str::FromStr,
 };
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "std")]
+use std::error::Error;
 use Operations::*;
 
 /// Represents a regex.
 #[derive(Debug, Clone)]
-pub struct Regex<V: Eq + Hash + Display + Copy + Clone + Debug> {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "V: Deserialize<'de> + Ord"))
+)]
+pub struct Regex<V: Eq + Hash + Display + Clone + Debug> {
     pub(crate) alphabet: HashSet<V>,
     pub(crate) regex: Operations<V>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub(crate) enum Operations<V: Eq + Hash + Display + Copy + Clone + Debug> {
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(bound(deserialize = "V: Deserialize<'de> + Ord"))
+)]
+pub(crate) enum Operations<V: Eq + Hash + Display + Clone + Debug> {
     Union(BTreeSet<Operations<V>>),
     Concat(VecDeque<Operations<V>>),
+    Intersect(BTreeSet<Operations<V>>),
+    Complement(Box<Operations<V>>),
     Repeat(Box<Operations<V>>, usize, Option<usize>),
     Letter(V),
     Epsilon,
@@ -33,30 +49,45 @@ pub(crate) enum Operations<V: Eq + Hash + Display + Copy + Clone + Debug> {
     Dot,
 }
 
+/// A regex compiled into its minimal automaton, to match many words without building it again.
+#[derive(Debug, Clone)]
+pub struct CompiledRegex<V: Eq + Hash + Display + Clone + Debug + Ord> {
+    dfa: DFA<V>,
+}
+
+/// The error returned when a string can't be parsed as a regex.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// The description of the error.
+    pub message: String,
+    /// The byte offset of the offending token in the parsed string.
+    pub position: usize,
+}
+
 /// An interface for structs that can be converted into a Regex.
-pub trait ToRegex<V: Eq + Hash + Display + Copy + Clone + Debug> {
+pub trait ToRegex<V: Eq + Hash + Display + Clone + Debug> {
     fn to_regex(&self) -> Regex<V>;
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> ToDfa<V> for Regex<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> ToDfa<V> for Regex<V> {
     fn to_dfa(&self) -> DFA<V> {
         self.to_nfa().to_dfa()
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> ToNfa<V> for Regex<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> ToNfa<V> for Regex<V> {
     fn to_nfa(&self) -> NFA<V> {
         self.regex.to_nfa(&self.alphabet)
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug> ToRegex<V> for Regex<V> {
+impl<V: Eq + Hash + Display + Clone + Debug> ToRegex<V> for Regex<V> {
     fn to_regex(&self) -> Regex<V> {
         self.clone()
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Regex<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Regex<V> {
     /// Simplify the regex.
     pub fn simplify(self) -> Regex<V> {
         let Regex { alphabet, regex } = self;
@@ -70,49 +101,202 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Regex<V> {
     pub fn contains(&self, other: &Regex<V>) -> bool {
         self.to_nfa().contains(&other.to_nfa())
     }
+
+    /// Returns `Ok` if `self` and `other` match the same words, otherwise the shortest word matched by exactly one of them.
+    pub fn equivalence_witness(&self, other: &Regex<V>) -> Result<(), Vec<V>> {
+        match self
+            .to_dfa()
+            .symmetric_difference(other.to_dfa())
+            .shortest_accepted()
+        {
+            Some(word) => Err(word),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the [Brzozowski derivative](https://en.wikipedia.org/wiki/Brzozowski_derivative) of the regex with respect to `letter`.
+    pub fn derivative(&self, letter: V) -> Regex<V> {
+        Regex {
+            alphabet: self.alphabet.clone(),
+            regex: self.regex.derivative(&letter, &self.alphabet),
+        }
+    }
+
+    /// Returns `true` if and only if the regex contains a `.`, which matches any letter of its alphabet.
+    pub fn uses_dot(&self) -> bool {
+        self.regex.uses_dot()
+    }
+
+    /// Returns the regex compiled into its minimal automaton, which is computed only once.
+    pub fn compile(self) -> CompiledRegex<V> {
+        CompiledRegex {
+            dfa: self.to_dfa().minimize(),
+        }
+    }
+
+    /// Returns the [star height](https://en.wikipedia.org/wiki/Star_height) of the regex, the maximum number of nested unbounded repetitions.
+    pub fn star_height(&self) -> usize {
+        self.regex.star_height()
+    }
+
+    /// Returns `true` if and only if `word` matches the regex, using derivatives instead of building an automaton.
+    /// Strings of `char` are matched by [`CompiledRegex::run_str`](struct.CompiledRegex.html#method.run_str), since this name already takes a slice of letters.
+    pub fn is_match(&self, word: &[V]) -> bool {
+        // without simplification the derivatives keep growing with the length of `word`
+        word.iter()
+            .fold(self.regex.clone().simplify(&self.alphabet), |acc, x| {
+                acc.derivative(x, &self.alphabet).simplify(&self.alphabet)
+            })
+            .nullable()
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> CompiledRegex<V> {
+    /// Returns `true` if and only if `word` matches the regex.
+    pub fn is_match(&self, word: &[V]) -> bool {
+        self.dfa.run(word)
+    }
+
+    /// A contains B if and only if for each `word` w, if B `accepts` w then A `accepts` w.
+    /// The cached automatons are explored together, without building any new automaton.
+    pub fn contains(&self, other: &CompiledRegex<V>) -> bool {
+        let (a, b) = (&self.dfa, &other.dfa);
+
+        // `None` stands for the missing sink state of `self`
+        let start = (Some(a.initial), b.initial);
+        let mut seen = HashSet::new();
+        seen.insert(start);
+        let mut stack = vec![start];
+        while let Some((p, q)) = stack.pop() {
+            if b.finals.contains(&q) && !p.map_or(false, |p| a.finals.contains(&p)) {
+                return false;
+            }
+            for (v, &q) in &b.transitions[q] {
+                let p = p.and_then(|p| a.transitions[p].get(v).cloned());
+                if seen.insert((p, q)) {
+                    stack.push((p, q));
+                }
+            }
+        }
+
+        true
+    }
+
+    /// Returns the shortest word matching the regex, or `None` if it matches nothing.
+    pub fn shortest_accepted(&self) -> Option<Vec<V>> {
+        self.dfa.shortest_accepted()
+    }
+
+    /// Returns the minimal automaton of the regex.
+    pub fn dfa(&self) -> &DFA<V> {
+        &self.dfa
+    }
+}
+
+impl CompiledRegex<char> {
+    /// Returns `true` if and only if the characters of `s` match the regex.
+    /// It isn't named `is_match` since [`is_match`](#method.is_match) already takes a slice of letters, like [`Regex::is_match`](struct.Regex.html#method.is_match).
+    /// A [`Regex`](struct.Regex.html) doesn't cache its automaton, so strings are matched on its compiled form: `regex.compile().run_str(s)`.
+    pub fn run_str(&self, s: &str) -> bool {
+        self.dfa.run_str(s)
+    }
 }
 
 impl Regex<char> {
+    /// Returns the regex written with the syntax accepted by the parser, escaping the letters that are operators.
+    /// Unlike [`to_string`](#method.to_string), no union of letters is shortened into a `.`,
+    /// but the regex should be parsed back with [`parse_with_alphabet`](#method.parse_with_alphabet) if it uses `.` or `~`.
+    pub fn to_parseable_string(&self) -> String {
+        self.regex.to_parseable_string()
+    }
+
+    fn parse_operations(
+        regex: &str,
+        alphabet: Option<&HashSet<char>>,
+    ) -> Result<Operations<char>, ParseError> {
+        let mut tokens = tokens(regex);
+        if peak(&tokens) == Some(Token::End) {
+            return Ok(Operations::Empty);
+        }
+
+        let regex = read_union(&mut tokens, alphabet)?;
+        if peak(&tokens) != Some(Token::End) {
+            Err(error(&tokens, "Trailing characters.".to_string()))
+        } else {
+            Ok(regex)
+        }
+    }
+
     /// Returns the Regex<char> struct corresponding to the given regex.
+    /// A negated character class `[^...]` stands for the letters of `alphabet` that are not listed.
+    /// Only the letters written in the regex are checked against `alphabet`, a `.` stands for any letter of `alphabet`.
     pub fn parse_with_alphabet(
         alphabet: HashSet<char>,
         regex: &str,
-    ) -> Result<Regex<char>, String> {
-        let mut tokens = tokens(regex);
-        if tokens.is_empty() {
-            return Ok(Regex {
+    ) -> Result<Regex<char>, ParseError> {
+        let operations = Regex::parse_operations(regex, Some(&alphabet))?;
+        if let Some(x) = operations
+            .alphabet()
+            .into_iter()
+            .find(|x| !alphabet.contains(x))
+        {
+            let position = tokens(regex)
+                .into_iter()
+                .find(|t| t.0 == Token::Letter && letter(t.1) == x)
+                .map_or(0, |t| t.2);
+            Err(ParseError {
+                message: format!("Letter {} is not in the given alphabet", x),
+                position,
+            })
+        } else {
+            Ok(Regex {
                 alphabet,
-                regex: Operations::Empty,
-            });
+                regex: operations,
+            })
         }
+    }
 
-        let regex = read_union(&mut tokens)?;
-        if !tokens.is_empty() {
-            Err("Trailing characters.".to_string())
-        } else if let Some(x) = regex.alphabet().into_iter().find(|x| !alphabet.contains(x)) {
-            Err(format!("Letter {} is not in the given alphabet", x))
-        } else {
-            Ok(Regex { alphabet, regex })
+    /// Returns a regex that matches only the given word, without interpreting any character as an operator.
+    pub fn literal(alphabet: HashSet<char>, word: &str) -> Result<Regex<char>, String> {
+        if let Some(x) = word.chars().find(|x| !alphabet.contains(x)) {
+            return Err(format!("Letter {} is not in the given alphabet", x));
         }
+
+        let mut letters: VecDeque<_> = word.chars().map(Letter).collect();
+        let regex = match letters.len() {
+            0 => Epsilon,
+            1 => letters.pop_front().unwrap(),
+            _ => Concat(letters),
+        };
+
+        Ok(Regex { alphabet, regex })
     }
 }
 
-/// Returns the Regex<char> struct corresponding to the given regex, the alphabet is composed of the letter used in the regexp (without '+', '*', '?', '.', '(', ')', '|', '𝜀').
+/// Returns the Regex<char> struct corresponding to the given regex, the alphabet is composed of the letters used in the regexp (including the ones of character classes).
+/// Since the alphabet isn't known beforehand, negated character classes `[^...]` are rejected.
 impl FromStr for Regex<char> {
-    type Err = String;
-
-    fn from_str(s: &str) -> Result<Regex<char>, String> {
-        let unauthorized: HashSet<char> = vec!['+', '*', '?', '.', '(', ')', '|', '𝜀']
-            .into_iter()
-            .collect();
+    type Err = ParseError;
 
-        let alphabet: HashSet<char> = s.chars().filter(|x| !unauthorized.contains(&x)).collect();
+    fn from_str(s: &str) -> Result<Regex<char>, ParseError> {
+        let regex = Regex::parse_operations(s, None)?;
+        Ok(Regex {
+            alphabet: regex.alphabet(),
+            regex,
+        })
+    }
+}
 
-        Regex::parse_with_alphabet(alphabet, s)
+impl Display for ParseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
+#[cfg(feature = "std")]
+impl Error for ParseError {}
+
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Operations<V> {
     fn simplify_union(t: BTreeSet<Operations<V>>, alphabet: &HashSet<V>) -> Operations<V> {
         if t.iter().all(|x| x == &Empty) {
             return Empty;
@@ -177,32 +361,59 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
             }
             Concat(vec![facto, Union(new_set)].into_iter().collect()).simplify(alphabet)
         } else {
-            Union(set)
+            Operations::factor_suffix(set, alphabet)
         }
     }
 
-    fn simplify_concat(v: VecDeque<Operations<V>>, alphabet: &HashSet<V>) -> Operations<V> {
-        if v.iter().all(|x| x == &Epsilon) {
-            return Epsilon;
+    // each factorization strictly shortens the members of the union so the recursion ends
+    fn factor_suffix(set: BTreeSet<Operations<V>>, alphabet: &HashSet<V>) -> Operations<V> {
+        let facto = match set.iter().next().unwrap() {
+            Concat(t) => t.back().unwrap(),
+            x => x,
         }
+        .clone();
+
+        if set.iter().all(|x| match x {
+            Concat(t) => &facto == t.back().unwrap(),
+            x => &facto == x,
+        }) {
+            let mut new_set = BTreeSet::new();
+            for e in set {
+                match e {
+                    Concat(mut t) => {
+                        t.pop_back();
+                        new_set.insert(Concat(t));
+                    }
+                    _ => {
+                        new_set.insert(Epsilon);
+                    }
+                }
+            }
+            Concat(vec![Union(new_set), facto].into_iter().collect()).simplify(alphabet)
+        } else {
+            Union(set)
+        }
+    }
 
+    fn simplify_concat(v: VecDeque<Operations<V>>, alphabet: &HashSet<V>) -> Operations<V> {
         let mut vec = VecDeque::with_capacity(v.len());
         for e in v.into_iter() {
             match e.simplify(alphabet) {
                 Epsilon => {}
                 Concat(v) => {
                     for e in v {
-                        vec.push_back(e);
+                        Operations::push_merging_repeats(&mut vec, e);
                     }
                 }
                 x => {
-                    vec.push_back(x);
+                    Operations::push_merging_repeats(&mut vec, x);
                 }
             }
         }
 
+        // every element matched only the empty word, like `R{0}` does
         if vec.is_empty() {
-            Empty
+            Epsilon
         } else if vec.len() == 1 {
             vec.pop_back().unwrap()
         } else {
@@ -210,6 +421,43 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
         }
     }
 
+    /// Pushes `x` at the end of the concatenation `vec`, merging `R{a,}R{b,}` into `R{a+b,}`, so that `R*R*` becomes `R*` and `R+R+` becomes `R{2,}`.
+    fn push_merging_repeats(vec: &mut VecDeque<Operations<V>>, x: Operations<V>) {
+        if let (Some(Repeat(o1, min1, None)), Repeat(o2, min2, None)) = (vec.back_mut(), &x) {
+            if o1 == o2 {
+                if let Some(min) = min1.checked_add(*min2) {
+                    *min1 = min;
+                    return;
+                }
+            }
+        }
+
+        vec.push_back(x);
+    }
+
+    fn simplify_intersect(t: BTreeSet<Operations<V>>, alphabet: &HashSet<V>) -> Operations<V> {
+        let mut set = BTreeSet::new();
+        for e in t.into_iter() {
+            match e.simplify(alphabet) {
+                Empty => return Empty,
+                Intersect(t) => {
+                    for e in t {
+                        set.insert(e);
+                    }
+                }
+                x => {
+                    set.insert(x);
+                }
+            }
+        }
+
+        if set.len() == 1 {
+            set.into_iter().next().unwrap()
+        } else {
+            Intersect(set)
+        }
+    }
+
     fn simplify_repeat(
         o: Operations<V>,
         min: usize,
@@ -217,8 +465,8 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
         alphabet: &HashSet<V>,
     ) -> Operations<V> {
         match (min, max, o.simplify(alphabet)) {
+            (min, Some(max), _) if max < min => Empty,
             (0, Some(0), _) | (_, _, Epsilon) => Epsilon,
-            (min, Some(max), _) if max < min => Epsilon,
             (1, Some(1), x) => x,
             (0, _, Empty) => Union(vec![Empty, Epsilon].into_iter().collect()),
             (_, _, Empty) => Empty,
@@ -235,6 +483,17 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
                 }
                 Union(u).simplify(alphabet)
             }
+            // (A*|B)* is (A|B)*, as is (A?|B)* or (A+|B)*
+            (0, None, Union(u)) if u.iter().any(|x| matches!(x, Repeat(_, 0..=1, _))) => {
+                let u = u
+                    .into_iter()
+                    .map(|x| match x {
+                        Repeat(o, 0..=1, _) => *o,
+                        x => x,
+                    })
+                    .collect();
+                Repeat(Box::new(Union(u)), 0, None).simplify(alphabet)
+            }
             (0, max, Union(mut u)) => {
                 u.remove(&Epsilon);
                 if u.is_empty() {
@@ -246,6 +505,10 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
                 }
             }
             (1, None, Repeat(o, 0, _)) => Repeat(o, 0, None),
+            // if A matches the empty word, A{n,} is A*
+            (min, None, x) if min > 0 && x.nullable() => {
+                Repeat(Box::new(x), 0, None).simplify(alphabet)
+            }
             (min, max, x) => Repeat(Box::new(x), min, max),
         }
     }
@@ -254,21 +517,77 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
         match self {
             Union(t) => Operations::simplify_union(t, alphabet),
             Concat(v) => Operations::simplify_concat(v, alphabet),
+            Intersect(t) => Operations::simplify_intersect(t, alphabet),
+            Complement(o) => match o.simplify(alphabet) {
+                Complement(o) => *o,
+                o => Complement(Box::new(o)),
+            },
             Repeat(o, min, max) => Operations::simplify_repeat(*o, min, max, alphabet),
             x => x,
         }
     }
 
+    /// Returns `true` if and only if the empty word matches `self`.
+    pub(crate) fn nullable(&self) -> bool {
+        match self {
+            Union(v) => v.iter().any(|x| x.nullable()),
+            Concat(v) => v.iter().all(|x| x.nullable()),
+            Intersect(v) => v.iter().all(|x| x.nullable()),
+            Complement(o) => !o.nullable(),
+            Repeat(_, min, Some(max)) if max < min => false,
+            Repeat(o, min, _) => *min == 0 || o.nullable(),
+            Epsilon => true,
+            Letter(_) | Empty | Dot => false,
+        }
+    }
+
+    pub(crate) fn derivative(&self, letter: &V, alphabet: &HashSet<V>) -> Operations<V> {
+        match self {
+            Union(v) => v
+                .iter()
+                .fold(Empty, |acc, x| acc + x.derivative(letter, alphabet)),
+            Concat(v) => {
+                let mut rest = v.clone();
+                if let Some(first) = rest.pop_front() {
+                    let rest = Concat(rest);
+                    let d = first.derivative(letter, alphabet) * rest.clone();
+                    if first.nullable() {
+                        d + rest.derivative(letter, alphabet)
+                    } else {
+                        d
+                    }
+                } else {
+                    Empty
+                }
+            }
+            Intersect(v) => Intersect(v.iter().map(|x| x.derivative(letter, alphabet)).collect()),
+            Complement(o) => Complement(Box::new(o.derivative(letter, alphabet))),
+            Repeat(_, _, Some(0)) => Empty,
+            Repeat(_, min, Some(max)) if max < min => Empty,
+            Repeat(o, min, max) => {
+                // if `o` is nullable then o{min,max} is the same as o{0,max}
+                let min = if o.nullable() {
+                    0
+                } else {
+                    min.saturating_sub(1)
+                };
+                let max = max.map(|x| x - 1);
+                o.derivative(letter, alphabet) * Repeat(o.clone(), min, max)
+            }
+            Letter(a) if a == letter => Epsilon,
+            Dot if alphabet.contains(letter) => Epsilon,
+            Letter(_) | Dot | Epsilon | Empty => Empty,
+        }
+    }
+
     fn to_nfa(&self, alphabet: &HashSet<V>) -> NFA<V> {
         match self {
-            Union(v) => v.iter().fold(NFA::new_empty(alphabet.clone()), |acc, x| {
-                acc.unite(x.to_nfa(alphabet))
+            Union(v) => NFA::union_all(alphabet.clone(), v.iter().map(|x| x.to_nfa(alphabet))),
+            Concat(v) => NFA::concat_all(alphabet.clone(), v.iter().map(|x| x.to_nfa(alphabet))),
+            Intersect(v) => v.iter().fold(NFA::new_full(alphabet.clone()), |acc, x| {
+                acc.intersect(x.to_nfa(alphabet))
             }),
-            Concat(v) => v
-                .iter()
-                .fold(NFA::new_length(alphabet.clone(), 0), |acc, x| {
-                    acc.concatenate(x.to_nfa(alphabet))
-                }),
+            Complement(o) => o.to_nfa(alphabet).negate(),
             Repeat(a, min, max) => {
                 if let Some(max) = max {
                     a.to_nfa(alphabet).repeat(*min..=(*max))
@@ -276,13 +595,14 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
                     a.to_nfa(alphabet).repeat((*min)..)
                 }
             }
-            Letter(a) => NFA::new_matching(alphabet.clone(), &[*a]),
+            Letter(a) => NFA::new_matching(alphabet.clone(), core::slice::from_ref(a)),
             Epsilon => NFA::new_length(alphabet.clone(), 0),
             Empty => NFA::new_empty(alphabet.clone()),
             Dot => NFA::new_length(alphabet.clone(), 1),
         }
     }
 
+    /// Returns the letters used in the regex, `Dot` excluded since it stands for any letter of the regex's alphabet.
     pub(crate) fn alphabet(&self) -> HashSet<V> {
         let mut stack = vec![self];
         let mut alphabet = HashSet::new();
@@ -291,9 +611,10 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
             match x {
                 Union(v) => v.iter().for_each(|x| stack.push(x)),
                 Concat(v) => v.iter().for_each(|x| stack.push(x)),
-                Repeat(o, _, _) => stack.push(&**o),
+                Intersect(v) => v.iter().for_each(|x| stack.push(x)),
+                Complement(o) | Repeat(o, _, _) => stack.push(&**o),
                 Letter(v) => {
-                    alphabet.insert(*v);
+                    alphabet.insert(v.clone());
                 }
                 _ => {}
             }
@@ -302,6 +623,28 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
         alphabet
     }
 
+    /// Returns the maximum number of nested unbounded repetitions in the regex.
+    pub(crate) fn star_height(&self) -> usize {
+        match self {
+            Union(v) | Intersect(v) => v.iter().map(|x| x.star_height()).max().unwrap_or(0),
+            Concat(v) => v.iter().map(|x| x.star_height()).max().unwrap_or(0),
+            Complement(o) | Repeat(o, _, Some(_)) => o.star_height(),
+            Repeat(o, _, None) => o.star_height() + 1,
+            Letter(_) | Epsilon | Empty | Dot => 0,
+        }
+    }
+
+    /// Returns `true` if and only if the regex contains a `Dot`.
+    pub(crate) fn uses_dot(&self) -> bool {
+        match self {
+            Union(v) | Intersect(v) => v.iter().any(|x| x.uses_dot()),
+            Concat(v) => v.iter().any(|x| x.uses_dot()),
+            Complement(o) | Repeat(o, _, _) => o.uses_dot(),
+            Dot => true,
+            _ => false,
+        }
+    }
+
     fn to_string(&self, alphabet: &HashSet<V>) -> String {
         match self {
             Union(v) => {
@@ -313,7 +656,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
                 }
 
                 let mut acc = String::new();
-                if alphabet.iter().all(|x| v.contains(&Letter(*x))) {
+                if alphabet.iter().all(|x| v.contains(&Letter(x.clone()))) {
                     acc.push('.');
                     acc.push('|');
                     for x in v.iter().filter(|x| match x {
@@ -333,6 +676,20 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
                 acc
             }
             Concat(v) => {
+                let mut acc = String::new();
+                for e in v {
+                    match e {
+                        Union(_) | Intersect(_) => {
+                            acc.push('(');
+                            acc.push_str(e.to_string(alphabet).as_str());
+                            acc.push(')');
+                        }
+                        _ => acc.push_str(e.to_string(alphabet).as_str()),
+                    }
+                }
+                acc
+            }
+            Intersect(v) => {
                 let mut acc = String::new();
                 for e in v {
                     match e {
@@ -343,9 +700,12 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
                         }
                         _ => acc.push_str(e.to_string(alphabet).as_str()),
                     }
+                    acc.push('&');
                 }
+                acc.pop();
                 acc
             }
+            Complement(a) => format!("~{}", paren!(a.to_string(alphabet))),
             Repeat(a, 0, None) => format!("{}*", paren!(a.to_string(alphabet))),
             Repeat(a, 1, None) => format!("{}+", paren!(a.to_string(alphabet))),
             Repeat(a, 0, Some(1)) => format!("{}?", paren!(a.to_string(alphabet))),
@@ -375,7 +735,52 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Operations<V> {
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Buildable<V> for Regex<V> {
+impl Operations<char> {
+    fn to_parseable_string(&self) -> String {
+        match self {
+            Union(v) if v.is_empty() => "∅".to_string(),
+            Union(v) => v
+                .iter()
+                .map(|x| x.to_parseable_string())
+                .collect::<Vec<_>>()
+                .join("|"),
+            Concat(v) if v.is_empty() => "𝜀".to_string(),
+            Concat(v) => v
+                .iter()
+                .map(|e| match e {
+                    Union(_) | Intersect(_) => format!("({})", e.to_parseable_string()),
+                    _ => e.to_parseable_string(),
+                })
+                .collect(),
+            Intersect(v) => v
+                .iter()
+                .map(|e| match e {
+                    Union(_) => format!("({})", e.to_parseable_string()),
+                    _ => e.to_parseable_string(),
+                })
+                .collect::<Vec<_>>()
+                .join("&"),
+            Complement(a) => format!("~{}", paren!(a.to_parseable_string())),
+            Repeat(a, 0, None) => format!("{}*", paren!(a.to_parseable_string())),
+            Repeat(a, 1, None) => format!("{}+", paren!(a.to_parseable_string())),
+            Repeat(a, 0, Some(1)) => format!("{}?", paren!(a.to_parseable_string())),
+            Repeat(a, min, Some(max)) if min == max => {
+                format!("{}{{{}}}", paren!(a.to_parseable_string()), min)
+            }
+            Repeat(a, min, Some(max)) => {
+                format!("{}{{{},{}}}", paren!(a.to_parseable_string()), min, max)
+            }
+            Repeat(a, min, None) => format!("{}{{{},}}", paren!(a.to_parseable_string()), min),
+            Letter(a) if "|&~+().*?𝜀∅{}[]\\".contains(*a) => format!("\\{}", a),
+            Letter(a) => a.to_string(),
+            Epsilon => "𝜀".to_string(),
+            Empty => "∅".to_string(),
+            Dot => ".".to_string(),
+        }
+    }
+}
+
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Buildable<V> for Regex<V> {
     fn unite(mut self, b: Regex<V>) -> Regex<V> {
         append_hashset(&mut self.alphabet, b.alphabet);
         self.regex += b.regex;
@@ -404,42 +809,48 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Buildable<V> for Regex
     }
 
     fn repeat<R: RangeBounds<usize>>(mut self, r: R) -> Regex<V> {
+        // an excluded bound of usize::MAX or 0 leaves no possible number of repetitions
         let start = match r.start_bound() {
-            Included(&a) => a,
-            Excluded(&a) => a + 1,
-            Unbounded => 0,
+            Included(&a) => Some(a),
+            Excluded(&a) => a.checked_add(1),
+            Unbounded => Some(0),
         };
 
         let end = match r.end_bound() {
-            Included(&a) => Some(a),
-            Excluded(&a) => Some(a - 1),
-            Unbounded => None,
+            Included(&a) => Some(Some(a)),
+            Excluded(&a) => a.checked_sub(1).map(Some),
+            Unbounded => Some(None),
         };
 
-        self.regex = Repeat(Box::new(self.regex), start, end);
+        self.regex = match (start, end) {
+            (Some(start), Some(end)) if end.map_or(true, |end| start <= end) => {
+                Repeat(Box::new(self.regex), start, end)
+            }
+            _ => Empty,
+        };
         self
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<Regex<V>> for Regex<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<Regex<V>> for Regex<V> {
     fn eq(&self, b: &Regex<V>) -> bool {
         self.le(&b) && self.ge(&b)
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<NFA<V>> for Regex<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<NFA<V>> for Regex<V> {
     fn eq(&self, b: &NFA<V>) -> bool {
         self.to_nfa().eq(b)
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<DFA<V>> for Regex<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<DFA<V>> for Regex<V> {
     fn eq(&self, b: &DFA<V>) -> bool {
         self.to_nfa().eq(&b.to_nfa())
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<Automaton<V>> for Regex<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialEq<Automaton<V>> for Regex<V> {
     fn eq(&self, b: &Automaton<V>) -> bool {
         match b {
             Automaton::DFA(v) => self.eq(&*v),
@@ -449,7 +860,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialEq<Automaton<V>
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialOrd for Regex<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> PartialOrd for Regex<V> {
     fn partial_cmp(&self, other: &Regex<V>) -> Option<Ordering> {
         match (self.ge(&other), self.le(&other)) {
             (true, true) => Some(Equal),
@@ -476,13 +887,13 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> PartialOrd for Regex<V
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> ToString for Regex<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> ToString for Regex<V> {
     fn to_string(&self) -> String {
         self.regex.to_string(&self.alphabet)
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Add for Regex<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Add for Regex<V> {
     type Output = Self;
 
     fn add(self, other: Regex<V>) -> Regex<V> {
@@ -490,7 +901,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Add for Regex<V> {
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Mul for Regex<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Mul for Regex<V> {
     type Output = Self;
 
     fn mul(self, other: Regex<V>) -> Regex<V> {
@@ -498,7 +909,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Mul for Regex<V> {
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Add for Operations<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> Add for Operations<V> {
     type Output = Self;
 
     fn add(self, other: Operations<V>) -> Operations<V> {
@@ -524,7 +935,7 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> Add for Operations<V>
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug> Mul for Operations<V> {
+impl<V: Eq + Hash + Display + Clone + Debug> Mul for Operations<V> {
     type Output = Self;
 
     fn mul(self, other: Operations<V>) -> Operations<V> {
@@ -550,10 +961,10 @@ impl<V: Eq + Hash + Display + Copy + Clone + Debug> Mul for Operations<V> {
     }
 }
 
-impl<V: Eq + Hash + Display + Copy + Clone + Debug + Ord> AddAssign for Operations<V> {
+impl<V: Eq + Hash + Display + Clone + Debug + Ord> AddAssign for Operations<V> {
     fn add_assign(&mut self, op: Operations<V>) {
         let mut tmp = Operations::Epsilon;
-        std::mem::swap(&mut tmp, self);
+        core::mem::swap(&mut tmp, self);
         *self = tmp + op;
     }
 }
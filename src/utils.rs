@@ -1,12 +1,12 @@
 /* AUXILIARY FUNCTIONS */
 
 use crate::{
+    compat::*,
     nfa::NFA,
     regex::{Operations, Operations::Letter},
 };
-use std::{
-    collections::{BTreeSet, HashMap, HashSet},
-    fmt::{Debug, Display},
+use core::{
+    fmt::{self, Debug, Display, Formatter},
     hash::Hash,
 };
 
@@ -24,10 +24,13 @@ pub fn shift_hashset(a: &mut HashSet<usize>, l: usize) {
     }
 }
 
-pub fn shift_fnda<V: Eq + Hash + Display + Copy + Clone + Debug + Ord>(a: &mut NFA<V>, l: usize) {
+pub fn shift_fnda<V: Eq + Hash + Display + Clone + Debug + Ord>(a: &mut NFA<V>, l: usize) {
     shift_hashset(&mut a.initials, l);
     shift_hashset(&mut a.finals, l);
     shift_transitions(&mut a.transitions, l);
+    for eps in &mut a.epsilon {
+        shift_hashset(eps, l);
+    }
 }
 
 pub fn shift_transitions<V: Eq + Hash>(a: &mut Vec<HashMap<V, Vec<usize>>>, l: usize) {
@@ -46,11 +49,19 @@ pub fn append_shift_transitions<V: Eq + Hash>(
     a.append(&mut b);
 }
 
-pub(crate) fn contains_dot<V: Eq + Hash + Display + Copy + Clone + Debug + Ord>(
+pub fn append_shift_epsilon(a: &mut Vec<HashSet<usize>>, mut b: Vec<HashSet<usize>>) {
+    let l = a.len();
+    for eps in &mut b {
+        shift_hashset(eps, l);
+    }
+    a.append(&mut b);
+}
+
+pub(crate) fn contains_dot<V: Eq + Hash + Display + Clone + Debug + Ord>(
     set: &BTreeSet<Operations<V>>,
     alphabet: &HashSet<V>,
 ) -> bool {
-    alphabet.iter().all(|x| set.contains(&Letter(*x)))
+    alphabet.iter().all(|x| set.contains(&Letter(x.clone())))
 }
 
 macro_rules! paren {
@@ -62,3 +73,92 @@ macro_rules! paren {
         }
     };
 }
+
+pub fn csv_field(s: &str) -> String {
+    if s.contains(&[',', '"', '\n', '\r'][..]) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+pub fn csv_fields(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        if quoted {
+            if c != '"' {
+                field.push(c);
+            } else if chars.peek() == Some(&'"') {
+                chars.next();
+                field.push('"');
+            } else {
+                quoted = false;
+            }
+        } else if c == '"' && field.is_empty() {
+            quoted = true;
+        } else if c == ',' {
+            fields.push(core::mem::take(&mut field));
+        } else {
+            field.push(c);
+        }
+    }
+
+    if quoted {
+        return Err(format!("Unterminated quoted field in {}", line));
+    }
+    fields.push(field);
+
+    Ok(fields)
+}
+
+/// Writes a transition table whose first column holds the states, the initial and final states being marked by `>` and `*`.
+pub fn write_table(
+    f: &mut Formatter<'_>,
+    header: Vec<String>,
+    rows: Vec<(usize, bool, bool, Vec<String>)>,
+) -> fmt::Result {
+    let rows: Vec<Vec<String>> = rows
+        .into_iter()
+        .map(|(state, initial, accepting, cells)| {
+            let mut name = String::new();
+            if initial {
+                name.push('>');
+            }
+            if accepting {
+                name.push('*');
+            }
+            name.push_str(&state.to_string());
+            let mut row = vec![name];
+            row.extend(cells);
+            row
+        })
+        .collect();
+
+    let mut widths: Vec<usize> = header.iter().map(|x| x.chars().count()).collect();
+    widths.insert(0, 0);
+    for row in &rows {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(cell.chars().count());
+        }
+    }
+
+    let mut line = format!("{:>1$}", "", widths[0]);
+    for (w, cell) in widths[1..].iter().zip(&header) {
+        line.push_str(&format!(" | {:1$}", cell, w));
+    }
+    writeln!(f, "{}", line.trim_end())?;
+
+    for row in rows {
+        let mut line = format!("{:>1$}", row[0], widths[0]);
+        for (w, cell) in widths[1..].iter().zip(&row[1..]) {
+            line.push_str(&format!(" | {:1$}", cell, w));
+        }
+        writeln!(f, "{}", line.trim_end())?;
+    }
+
+    Ok(())
+}
@@ -1,13 +1,15 @@
-use rand::prelude::*;
+use rand::{prelude::*, rngs::StdRng};
+use rustomaton::regex::Regex;
 use std::cmp::Ordering::{Equal, Greater, Less};
 use std::collections::HashSet;
 
 #[derive(Debug)]
-pub struct Generator {
+pub struct Generator<R: Rng = ThreadRng> {
     alphabet: Vec<char>,
     max_depth: u8,
     actual_depth: u8,
-    rng: ThreadRng,
+    min_letters: usize,
+    rng: R,
 }
 
 pub fn new_generator(alphabet: HashSet<char>, max_depth: u8) -> Generator {
@@ -15,12 +17,28 @@ pub fn new_generator(alphabet: HashSet<char>, max_depth: u8) -> Generator {
         alphabet: alphabet.into_iter().collect(),
         max_depth,
         actual_depth: 0,
+        min_letters: 0,
         rng: rand::thread_rng(),
     }
 }
 
-impl Generator {
-    fn random_with_rng(alphabet: &[char], rng: &mut ThreadRng) -> String {
+impl Generator<StdRng> {
+    pub fn from_seed(alphabet: HashSet<char>, max_depth: u8, seed: u64) -> Generator<StdRng> {
+        // the alphabet is sorted so that the seed alone determines the output
+        let mut alphabet: Vec<char> = alphabet.into_iter().collect();
+        alphabet.sort();
+        Generator {
+            alphabet,
+            max_depth,
+            actual_depth: 0,
+            min_letters: 0,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl<R: Rng> Generator<R> {
+    fn random_with_rng(alphabet: &[char], rng: &mut R) -> String {
         let alphalen = alphabet.len();
         let n = rng.gen_range(0, alphalen + 2);
 
@@ -35,7 +53,43 @@ impl Generator {
         Self::random_with_rng(&self.alphabet, &mut self.rng)
     }
 
+    pub fn min_letters(mut self, min_letters: usize) -> Self {
+        // a pattern of depth d contains at most 2^d letters
+        assert!(
+            self.max_depth >= 64 || min_letters as u64 <= 1 << self.max_depth,
+            "a depth of {} can't produce {} letters",
+            self.max_depth,
+            min_letters
+        );
+        self.min_letters = min_letters;
+        self
+    }
+
     pub fn run(&mut self) -> String {
+        loop {
+            let ret = self.generate();
+            let letters = ret.chars().filter(|x| self.alphabet.contains(x)).count();
+            if letters >= self.min_letters {
+                return ret;
+            }
+        }
+    }
+
+    pub fn run_nontrivial(&mut self) -> String {
+        let alphabet: HashSet<char> = self.alphabet.iter().cloned().collect();
+        loop {
+            let ret = self.run();
+            let regex = Regex::parse_with_alphabet(alphabet.clone(), &ret)
+                .unwrap()
+                .simplify()
+                .to_string();
+            if regex != "𝜀" && regex != "∅" {
+                return ret;
+            }
+        }
+    }
+
+    fn generate(&mut self) -> String {
         if self.actual_depth == self.max_depth {
             return self.letter();
         }
@@ -43,7 +97,7 @@ impl Generator {
         const TOTAL: u8 = 7;
         let choice = self.rng.gen_range(0, TOTAL);
         self.actual_depth += 1;
-        let rec1 = self.run();
+        let rec1 = self.generate();
 
         let ret = if choice < 5 {
             if choice == 0 {
@@ -58,7 +112,7 @@ impl Generator {
                 self.letter()
             }
         } else {
-            let rec2 = self.run();
+            let rec2 = self.generate();
             if choice == 5 {
                 format!("{}{}", rec1, rec2)
             } else {
@@ -1,14 +1,18 @@
+// the tests use the collections of `std`, which are those of the API only with the `std` feature
+#![cfg(feature = "std")]
+
 mod generator;
 
 #[cfg(test)]
 mod tests {
-    use super::generator::new_generator;
+    use super::generator::{new_generator, Generator};
     use rustomaton::automaton::{Automata, Buildable};
-    use rustomaton::dfa::ToDfa;
-    use rustomaton::nfa::{ToNfa, NFA};
+    use rustomaton::dfa::{ToDfa, DFA};
+    use rustomaton::nfa::{DotOptions, NFABuilder, ToNfa, NFA};
     use rustomaton::regex::{Regex, ToRegex};
-    use std::collections::{HashMap, HashSet};
+    use std::collections::{BTreeSet, HashMap, HashSet};
     use std::iter::repeat;
+    use std::ops::Bound::{Excluded, Unbounded};
 
     // empty automaton
     // this automaton is deterministic
@@ -277,6 +281,24 @@ mod tests {
         ]
     }
 
+    // the n-th letter from the end is an 'a': the subset construction creates 2^n sets
+    fn nth_letter_from_end(n: usize) -> NFA<char> {
+        let mut aut = NFA::new_empty(['a', 'b'].iter().cloned().collect());
+        for _ in 0..=n {
+            aut.add_state();
+        }
+        aut.add_initial(0);
+        aut.add_final(n);
+        aut.add_transition(0, 'a', 0);
+        aut.add_transition(0, 'b', 0);
+        aut.add_transition(0, 'a', 1);
+        for i in 1..n {
+            aut.add_transition(i, 'a', i + 1);
+            aut.add_transition(i, 'b', i + 1);
+        }
+        aut
+    }
+
     fn automaton_list() -> Vec<(NFA<char>, Vec<Vec<char>>, Vec<Vec<char>>)> {
         vec![
             (automaton0(), automaton0_accept(), automaton0_reject()),
@@ -298,6 +320,155 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_add_state() {
+        let mut aut = NFA::new_empty(HashSet::new());
+        let s0 = aut.add_state();
+        let s1 = aut.add_state();
+        aut.add_initial(s0);
+        aut.add_final(s1);
+        aut.add_transition(s0, 'a', s1);
+        aut.add_transition(s1, 'b', s1);
+        aut.add_transition(s1, 'b', s1);
+
+        assert_eq!((s0, s1), (0, 1));
+        assert!(aut.run(&['a', 'b', 'b']));
+        assert!(!aut.run(&['b']));
+        assert!(aut
+            .eq(&Regex::parse_with_alphabet(['a', 'b'].iter().copied().collect(), "ab*").unwrap()));
+    }
+
+    #[test]
+    fn test_accessors() {
+        let mut aut = automaton2();
+        aut.add_epsilon(0, 2);
+
+        assert_eq!(aut.num_states(), 3);
+        assert_eq!(aut.alphabet().len(), 10);
+        assert_eq!(aut.initials(), &(0..=0).collect::<HashSet<_>>());
+        assert_eq!(aut.finals(), &(0..=0).collect::<HashSet<_>>());
+
+        let mut transitions: Vec<_> = aut.transitions().collect();
+        transitions.sort();
+        assert_eq!(
+            transitions,
+            vec![
+                (0, '0', 0),
+                (0, '1', 1),
+                (1, '0', 2),
+                (1, '1', 0),
+                (2, '0', 1),
+                (2, '1', 2)
+            ]
+        );
+        assert_eq!(aut.epsilon_transitions().collect::<Vec<_>>(), vec![(0, 2)]);
+    }
+
+    #[test]
+    fn test_epsilon() {
+        let alphabet: HashSet<char> = ['a', 'b'].iter().copied().collect();
+        // a*b* with an epsilon transition between the two loops
+        let mut aut = NFA::new_empty(alphabet.clone());
+        let s0 = aut.add_state();
+        let s1 = aut.add_state();
+        aut.add_initial(s0);
+        aut.add_final(s1);
+        aut.add_transition(s0, 'a', s0);
+        aut.add_transition(s1, 'b', s1);
+        aut.add_epsilon(s0, s1);
+
+        let expected = Regex::parse_with_alphabet(alphabet.clone(), "a*b*").unwrap();
+        assert_eq!(
+            aut.epsilon_closure(&(0..=0).collect()),
+            (0..=1).collect::<HashSet<_>>()
+        );
+        assert!(aut.run(&[]));
+        assert!(aut.run(&['a', 'a', 'b']));
+        assert!(!aut.run(&['b', 'a']));
+        assert!(aut.to_dfa().eq(&expected));
+        assert!(aut.clone().remove_epsilon().eq(&expected));
+        assert!(aut
+            .clone()
+            .reverse()
+            .eq(&Regex::parse_with_alphabet(alphabet.clone(), "b*a*").unwrap()));
+
+        let other = Regex::parse_with_alphabet(alphabet.clone(), "ab")
+            .unwrap()
+            .to_nfa();
+        assert!(aut
+            .clone()
+            .unite(other.clone())
+            .eq(&Regex::parse_with_alphabet(alphabet.clone(), "a*b*|ab").unwrap()));
+        assert!(other
+            .clone()
+            .concatenate(aut.clone())
+            .eq(&Regex::parse_with_alphabet(alphabet.clone(), "aba*b*").unwrap()));
+        assert!(aut
+            .clone()
+            .concatenate(other)
+            .eq(&Regex::parse_with_alphabet(alphabet.clone(), "a*b*ab").unwrap()));
+        assert!(aut.clone().kleene().eq(&expected.clone().kleene()));
+        assert!(aut.to_regex().eq(&expected));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_add_transition_invalid() {
+        let mut aut = NFA::new_empty(HashSet::new());
+        let s0 = aut.add_state();
+        aut.add_transition(s0, 'a', 1);
+    }
+
+    #[test]
+    fn test_builder() {
+        let aut = NFABuilder::new(['a', 'b'].iter().copied().collect())
+            .state()
+            .transition(0, 'a', 1)
+            .transition(1, 'b', 1)
+            .initial(0)
+            .final_state(1)
+            .build()
+            .unwrap();
+        assert!(aut.run(&['a', 'b', 'b']));
+        assert!(!aut.run(&['b']));
+
+        assert!(NFABuilder::new(['a'].iter().copied().collect())
+            .transition(0, 'b', 0)
+            .build()
+            .is_err());
+        assert!(NFABuilder::<char>::new(HashSet::new())
+            .state()
+            .final_state(1)
+            .build()
+            .is_err());
+    }
+
+    #[test]
+    fn test_from_raw_error() {
+        let err = NFA::from_raw(
+            ['a'].iter().copied().collect(),
+            HashSet::new(),
+            HashSet::new(),
+            vec![[('a', vec![3])].iter().cloned().collect()],
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Transition from 0 to 3 labeled by a leads to a state that doesn't exist"
+        );
+
+        let err: Box<dyn std::error::Error> = Box::new(
+            NFA::from_raw(
+                ['a'].iter().copied().collect(),
+                (2..=2).collect(),
+                HashSet::new(),
+                Vec::new(),
+            )
+            .unwrap_err(),
+        );
+        assert_eq!(err.to_string(), "Initial state 2 doesn't exist");
+    }
+
     #[test]
     fn test_is_complete() {
         assert!(!automaton0().is_complete());
@@ -405,16 +576,307 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_complement_over() {
+        let alphabet: HashSet<char> = ['a', 'b', 'c'].iter().copied().collect();
+        let aut = NFA::new_full(['a', 'b'].iter().copied().collect()).to_dfa();
+        assert!(aut.clone().negate().is_empty());
+
+        let complement = aut.complement_over(&alphabet);
+        assert!(complement.run(&['c']));
+        assert!(complement.run(&['a', 'b', 'c', 'a']));
+        assert!(!complement.run(&['a', 'b', 'a']));
+        assert!(!complement.run(&[]));
+
+        for (i, (aut, acc, rej)) in automaton_list().into_iter().enumerate() {
+            let aut = aut.to_dfa().complement_over(&alphabet);
+            if let Some(e) = acc.iter().find(|x| aut.run(x)) {
+                panic!("complement of {} shouldn't accept {:?}", i, e);
+            }
+            if let Some(e) = rej.iter().find(|x| !aut.run(x)) {
+                panic!("complement of {} should accept {:?}", i, e);
+            }
+            if !aut.run(&['a']) {
+                panic!("complement of {} should accept a", i);
+            }
+        }
+    }
+
     #[test]
     fn test_intersect() {
         let list = automaton_list();
-        for (i, (aut1, _, rej1)) in list.iter().enumerate() {
+        for (i, (aut1, acc1, rej1)) in list.iter().enumerate() {
             for (j, (aut2, _, rej2)) in list.iter().enumerate() {
                 let aut = aut1.clone().intersect(aut2.clone());
                 if let Some(e) = rej1.iter().chain(rej2.iter()).find(|x| aut.run(x)) {
                     let dot = aut.to_dot();
                     panic!("intersection of {} and {}: elem {:?}\n\n{}", i, j, e, dot);
                 }
+                if let Some(e) = acc1.iter().find(|x| aut2.run(x) && !aut.run(x)) {
+                    let dot = aut.to_dot();
+                    panic!("intersection of {} and {}: elem {:?}\n\n{}", i, j, e, dot);
+                }
+
+                let neg = aut1.clone().negate().unite(aut2.clone().negate()).negate();
+                if !aut.eq(&neg) {
+                    panic!("intersection of {} and {} isn't the negation of the union of the negations", i, j);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_difference() {
+        let list = automaton_list();
+        for (i, (aut1, acc1, rej1)) in list.iter().enumerate() {
+            for (j, (aut2, acc2, rej2)) in list.iter().enumerate() {
+                let aut = aut1.clone().difference(aut2.clone());
+                if let Some(e) = acc1
+                    .iter()
+                    .chain(acc2.iter())
+                    .chain(rej1.iter())
+                    .chain(rej2.iter())
+                    .find(|x| aut.run(x) != (aut1.run(x) && !aut2.run(x)))
+                {
+                    let dot = aut.to_dot();
+                    panic!("difference of {} and {}: elem {:?}\n\n{}", i, j, e, dot);
+                }
+                if !aut.eq(&aut1.clone().intersect(aut2.clone().negate())) {
+                    panic!(
+                        "difference of {} and {} isn't the intersection with the negation",
+                        i, j
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_symmetric_difference() {
+        let list = automaton_list();
+        for (i, (aut1, acc1, _)) in list.iter().enumerate() {
+            for (j, (aut2, acc2, _)) in list.iter().enumerate() {
+                let aut = aut1.clone().symmetric_difference(aut2.clone());
+                if let Some(e) = acc1
+                    .iter()
+                    .chain(acc2.iter())
+                    .find(|x| aut.run(x) != (aut1.run(x) != aut2.run(x)))
+                {
+                    let dot = aut.to_dot();
+                    panic!(
+                        "symmetric difference of {} and {}: elem {:?}\n\n{}",
+                        i, j, e, dot
+                    );
+                }
+                if aut.is_empty() != aut1.eq(aut2) {
+                    panic!("symmetric difference of {} and {} disagrees with eq", i, j);
+                }
+                if aut.is_empty() != aut1.to_dfa().symmetric_difference(aut2.to_dfa()).is_empty() {
+                    panic!(
+                        "symmetric difference of {} and {} disagrees with the DFA one",
+                        i, j
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_shuffle() {
+        let alphabet: HashSet<char> = ['a', 'b', 'c'].iter().copied().collect();
+        let aut = NFA::new_matching(alphabet.clone(), &['a', 'b'])
+            .shuffle(NFA::new_matching(alphabet, &['c']));
+
+        for e in &[['c', 'a', 'b'], ['a', 'c', 'b'], ['a', 'b', 'c']] {
+            if !aut.run(e) {
+                panic!("the shuffle should accept {:?}\n\n{}", e, aut.to_dot());
+            }
+        }
+        for e in &[
+            vec!['b', 'a', 'c'],
+            vec!['a', 'b'],
+            vec!['c'],
+            vec!['c', 'b', 'a'],
+        ] {
+            if aut.run(e) {
+                panic!("the shuffle shouldn't accept {:?}\n\n{}", e, aut.to_dot());
+            }
+        }
+
+        let list = automaton_list();
+        for (i, (aut1, acc1, _)) in list.iter().enumerate() {
+            for (j, (aut2, acc2, _)) in list.iter().enumerate() {
+                let aut = aut1.clone().shuffle(aut2.clone());
+                for a1 in acc1 {
+                    for a2 in acc2 {
+                        let mut e = a1.clone();
+                        e.append(&mut a2.clone());
+                        if !aut.run(&e) {
+                            panic!("shuffle of {} and {} should accept {:?}", i, j, e);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_left_quotient() {
+        for (i, (aut, acc, _)) in automaton_list().into_iter().enumerate() {
+            for w in &acc {
+                for k in 0..=w.len() {
+                    let quotient = aut.left_quotient(&w[..k]);
+                    if !quotient.run(&w[k..]) {
+                        panic!(
+                            "{} quotiented by {:?} should accept {:?}",
+                            i,
+                            &w[..k],
+                            &w[k..]
+                        );
+                    }
+                }
+            }
+        }
+
+        let aut = automaton2();
+        assert!(aut.left_quotient(&['2']).is_empty());
+        assert!(aut.left_quotient(&['1']).run(&['1']));
+        assert!(!aut.left_quotient(&['1']).run(&['0']));
+    }
+
+    #[test]
+    fn test_right_quotient() {
+        let list = automaton_list();
+        for (i, (aut1, acc1, _)) in list.iter().enumerate() {
+            for (j, (aut2, _, _)) in list.iter().enumerate() {
+                let quotient = aut1.to_dfa().right_quotient(aut2);
+                for w in acc1 {
+                    for k in 0..=w.len() {
+                        if aut2.run(&w[k..]) && !quotient.run(&w[..k]) {
+                            panic!("{} quotiented by {} should accept {:?}", i, j, &w[..k]);
+                        }
+                    }
+                }
+            }
+        }
+
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let aut = Regex::parse_with_alphabet(alphabet.clone(), "12(3|45)")
+            .unwrap()
+            .to_dfa();
+        let suffix = Regex::parse_with_alphabet(alphabet.clone(), "3|5")
+            .unwrap()
+            .to_nfa();
+        let expected = Regex::parse_with_alphabet(alphabet, "12|124")
+            .unwrap()
+            .to_dfa();
+        assert!(aut.right_quotient(&suffix).eq(&expected));
+    }
+
+    #[test]
+    fn test_prefix_closure() {
+        for (i, (aut, acc, _)) in automaton_list().into_iter().enumerate() {
+            let closure = aut.clone().prefix_closure();
+            for w in &acc {
+                if let Some(k) = (0..=w.len()).find(|k| !closure.run(&w[..*k])) {
+                    panic!("{} prefix closed should accept {:?}", i, &w[..k]);
+                }
+            }
+            if closure.run(&[]) == aut.is_empty() {
+                panic!(
+                    "{} prefix closed should accept the empty word iff it isn't empty",
+                    i
+                );
+            }
+        }
+
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let closure = NFA::new_matching(alphabet, &['1', '2', '3']).prefix_closure();
+        assert!(closure.run(&['1', '2']));
+        assert!(!closure.run(&['2', '3']));
+        assert!(!closure.run(&['1', '2', '3', '4']));
+    }
+
+    #[test]
+    fn test_suffix_factor_closure() {
+        for (i, (aut, acc, _)) in automaton_list().into_iter().enumerate() {
+            let suffix = aut.clone().suffix_closure();
+            let factor = aut.clone().factor_closure();
+            for w in &acc {
+                if let Some(k) = (0..=w.len()).find(|k| !suffix.run(&w[*k..])) {
+                    panic!("{} suffix closed should accept {:?}", i, &w[k..]);
+                }
+                for k in 0..=w.len() {
+                    if let Some(l) = (k..=w.len()).find(|l| !factor.run(&w[k..*l])) {
+                        panic!("{} factor closed should accept {:?}", i, &w[k..l]);
+                    }
+                }
+            }
+        }
+
+        let alphabet: HashSet<char> = ['a', 'b', 'c'].iter().copied().collect();
+        let suffix = NFA::new_matching(alphabet.clone(), &['a', 'b', 'c']).suffix_closure();
+        assert!(suffix.run(&['b', 'c']));
+        assert!(suffix.run(&[]));
+        assert!(!suffix.run(&['a', 'b']));
+
+        let factor = NFA::new_matching(alphabet, &['a', 'b', 'c']).factor_closure();
+        for e in &[vec!['b'], vec!['b', 'c'], vec!['a', 'b'], vec![]] {
+            assert!(factor.run(e));
+        }
+        assert!(!factor.run(&['a', 'c']));
+    }
+
+    #[test]
+    fn test_map_alphabet() {
+        for (i, (aut, acc, rej)) in automaton_list().into_iter().enumerate() {
+            let mapped = aut.map_alphabet(|x| x as u8 - b'0');
+            if let Some(e) = acc
+                .iter()
+                .find(|x| !mapped.run(&x.iter().map(|&y| y as u8 - b'0').collect::<Vec<_>>()))
+            {
+                panic!("{} mapped should accept {:?}", i, e);
+            }
+            if let Some(e) = rej
+                .iter()
+                .find(|x| mapped.run(&x.iter().map(|&y| y as u8 - b'0').collect::<Vec<_>>()))
+            {
+                panic!("{} mapped shouldn't accept {:?}", i, e);
+            }
+        }
+
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let small = Regex::parse_with_alphabet(alphabet, "18|27")
+            .unwrap()
+            .to_nfa()
+            .map_alphabet(|x| x < '5');
+        assert!(small.run(&[true, false]));
+        assert!(!small.run(&[true, true]));
+        assert!(!small.run(&[false, true]));
+    }
+
+    #[test]
+    fn test_project_out() {
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let projected = Regex::parse_with_alphabet(alphabet.clone(), "1(02)*3|0+4|50")
+            .unwrap()
+            .to_nfa()
+            .project_out('0');
+        let expected = Regex::parse_with_alphabet(alphabet.clone(), "12*3|4|5")
+            .unwrap()
+            .to_nfa();
+        if !projected.eq(&expected) {
+            panic!("wrong projection\n\n{}", projected.to_dot());
+        }
+        assert!(!projected.run(&['0']));
+
+        for (i, (aut, acc, _)) in automaton_list().into_iter().enumerate() {
+            let projected = aut.project_out('1');
+            for w in &acc {
+                let e: Vec<char> = w.iter().copied().filter(|x| *x != '1').collect();
+                if !projected.run(&e) {
+                    panic!("{} without 1 should accept {:?}", i, e);
+                }
             }
         }
     }
@@ -518,6 +980,230 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_words() {
+        for (i, (aut, _, _)) in automaton_list().into_iter().enumerate() {
+            let words: Vec<Vec<char>> = aut.words().take(50).collect();
+            if let Some(e) = words.iter().find(|x| !aut.run(x)) {
+                panic!("{} enumerated {:?} which it doesn't accept", i, e);
+            }
+            for w in words.windows(2) {
+                if (w[0].len(), &w[0]) >= (w[1].len(), &w[1]) {
+                    panic!("{} enumerated {:?} before {:?}", i, w[0], w[1]);
+                }
+            }
+            if words.len() != aut.to_dfa().words().take(50).count() {
+                panic!("{} should enumerate the same words as its DFA", i);
+            }
+        }
+
+        assert_eq!(automaton0().words().next(), None);
+
+        let aut = Regex::parse_with_alphabet((b'0'..=b'9').map(char::from).collect(), "12|0|3?")
+            .unwrap()
+            .to_nfa();
+        let words: Vec<Vec<char>> = aut.words().collect();
+        assert_eq!(words, vec![vec![], vec!['0'], vec!['3'], vec!['1', '2']]);
+    }
+
+    #[test]
+    fn test_shortest_accepted() {
+        for (i, (aut, acc, _)) in automaton_list().into_iter().enumerate() {
+            match aut.shortest_accepted() {
+                None => {
+                    if !aut.is_empty() {
+                        panic!("{} isn't empty but has no shortest word", i);
+                    }
+                }
+                Some(w) => {
+                    if !aut.run(&w) {
+                        panic!("{} doesn't accept its shortest word {:?}", i, w);
+                    }
+                    if let Some(e) = acc.iter().find(|x| x.len() < w.len()) {
+                        panic!("{} accepts {:?} which is shorter than {:?}", i, e, w);
+                    }
+                    if aut.to_dfa().shortest_accepted().map(|x| x.len()) != Some(w.len()) {
+                        panic!("{} and its DFA have different shortest words", i);
+                    }
+                }
+            }
+        }
+
+        assert_eq!(automaton1().shortest_accepted(), Some(vec![]));
+        assert_eq!(automaton6().shortest_accepted().map(|x| x.len()), Some(1));
+    }
+
+    #[test]
+    fn test_count_words_of_length() {
+        for (i, (aut, _, _)) in automaton_list().into_iter().enumerate() {
+            let dfa = aut.to_dfa();
+            for len in 0..4 {
+                let count = dfa
+                    .words()
+                    .take_while(|x| x.len() <= len)
+                    .filter(|x| x.len() == len)
+                    .count();
+                if dfa.count_words_of_length(len) != count as u128 {
+                    panic!("{} should accept {} words of length {}", i, count, len);
+                }
+            }
+        }
+
+        assert_eq!(automaton0().to_dfa().count_words_of_length(3), 0);
+        assert_eq!(automaton1().to_dfa().count_words_of_length(3), 1000);
+        assert_eq!(automaton1().to_dfa().count_words_of_length(100), u128::MAX);
+    }
+
+    #[test]
+    fn test_is_finite() {
+        assert!(automaton0().is_finite());
+        assert!(!automaton1().is_finite());
+        assert!(!automaton2().is_finite());
+        assert!(!automaton3().is_finite());
+        assert!(!automaton4().is_finite());
+
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        assert!(NFA::new_matching(alphabet.clone(), &['1', '2']).is_finite());
+        assert!(NFA::new_empty_word(alphabet.clone()).is_finite());
+        assert!(
+            Regex::parse_with_alphabet(alphabet.clone(), "(12|3)(4|56?)")
+                .unwrap()
+                .to_dfa()
+                .is_finite()
+        );
+        assert!(!Regex::parse_with_alphabet(alphabet, "12|34*")
+            .unwrap()
+            .to_dfa()
+            .is_finite());
+    }
+
+    #[test]
+    fn test_count_accepted() {
+        assert_eq!(automaton0().to_dfa().count_accepted(), Some(0));
+        assert_eq!(automaton1().to_dfa().count_accepted(), None);
+        assert_eq!(automaton4().to_dfa().count_accepted(), None);
+
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let aut = Regex::parse_with_alphabet(alphabet.clone(), "(12|3)(4|56?)|𝜀")
+            .unwrap()
+            .to_dfa();
+        assert_eq!(aut.count_accepted(), Some(aut.words().count()));
+        assert_eq!(aut.count_accepted(), Some(7));
+        assert_eq!(
+            NFA::new_length(alphabet, 2).to_dfa().count_accepted(),
+            Some(100)
+        );
+    }
+
+    #[test]
+    fn test_contains_witness() {
+        let list = automaton_list();
+        for (i, (aut1, _, _)) in list.iter().enumerate() {
+            for (j, (aut2, _, _)) in list.iter().enumerate() {
+                match aut1.contains_witness(aut2) {
+                    Ok(()) => {
+                        if let Some(w) = aut2.words().take(20).find(|x| !aut1.run(x)) {
+                            panic!("{} doesn't contain {} but {:?} wasn't found", i, j, w);
+                        }
+                    }
+                    Err(w) => {
+                        if aut1.run(&w) || !aut2.run(&w) {
+                            panic!("{:?} isn't a witness that {} doesn't contain {}", w, i, j);
+                        }
+                    }
+                }
+            }
+        }
+
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let aut1 = Regex::parse_with_alphabet(alphabet.clone(), "1*")
+            .unwrap()
+            .to_nfa();
+        let aut2 = Regex::parse_with_alphabet(alphabet, "1*|2|345")
+            .unwrap()
+            .to_nfa();
+        assert_eq!(aut1.contains_witness(&aut2), Err(vec!['2']));
+        assert_eq!(aut2.contains_witness(&aut1), Ok(()));
+    }
+
+    #[test]
+    fn test_minimize_hopcroft() {
+        for (i, (aut, acc, rej)) in automaton_list().into_iter().enumerate() {
+            let aut1 = aut.to_dfa().minimize_hopcroft();
+
+            if let Some(e) = acc.iter().find(|x| !aut1.run(x)) {
+                panic!("{} minimized should accept {:?}", i, e);
+            }
+            if let Some(e) = rej.iter().find(|x| aut1.run(x)) {
+                panic!("{} minimized shouldn't accept {:?}", i, e);
+            }
+
+            let aut2 = aut.to_dfa().minimize();
+            if !aut1.eq(&aut2) {
+                panic!("{} minimized with both algorithms should be equal", i);
+            }
+        }
+
+        let aut = automaton_mult(500, 10, 0).to_dfa();
+        let aut1 = aut.clone().minimize_hopcroft();
+        let aut2 = aut.minimize();
+        assert!(aut1.eq(&aut2));
+        assert_eq!(aut1.count_words_of_length(4), aut2.count_words_of_length(4));
+    }
+
+    #[test]
+    fn test_canonical() {
+        let list = automaton_list();
+        for (i, (aut1, _, _)) in list.iter().enumerate() {
+            let dfa = aut1.to_dfa();
+            if !dfa.is_equivalent(&aut1.clone().reverse().reverse().to_dfa()) {
+                panic!("{} should be equivalent to itself reversed twice", i);
+            }
+            if !dfa.clone().canonical().eq(&dfa) {
+                panic!("{} should be equal to its canonical form", i);
+            }
+
+            for (j, (aut2, _, _)) in list.iter().enumerate() {
+                if dfa.is_equivalent(&aut2.to_dfa()) != aut1.eq(aut2) {
+                    panic!("is_equivalent disagrees with eq on {} and {}", i, j);
+                }
+            }
+        }
+
+        let mut set = HashSet::new();
+        set.insert(automaton2().to_dfa());
+        set.insert(automaton2().kleene().to_dfa());
+        set.insert(automaton1().to_dfa());
+        set.insert(automaton1().to_dfa().complete());
+        set.insert(automaton0().to_dfa());
+        assert_eq!(set.len(), 3);
+    }
+
+    #[test]
+    fn test_is_isomorphic() {
+        let list = automaton_list();
+        for (i, (aut, _, _)) in list.iter().enumerate() {
+            let dfa = aut.to_dfa();
+            if !dfa.is_isomorphic(&dfa) {
+                panic!("{} should be isomorphic to itself", i);
+            }
+
+            let minimal = dfa.clone().minimize();
+            if !minimal.is_isomorphic(&minimal.clone().canonical()) {
+                panic!("{} minimized should be isomorphic to its canonical form", i);
+            }
+        }
+
+        let aut1 = automaton2().to_dfa();
+        let aut2 = automaton2().kleene().to_dfa().minimize();
+        assert!(aut1.eq(&aut2));
+        assert!(aut1.minimize().is_isomorphic(&aut2));
+        assert!(!automaton2().to_dfa().is_isomorphic(&automaton1().to_dfa()));
+        assert!(!automaton2()
+            .to_dfa()
+            .is_isomorphic(&automaton2().to_dfa().complete().negate()));
+    }
+
     #[test]
     #[ignore]
     fn test_generator() {
@@ -527,6 +1213,39 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_generator_seed() {
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let mut gen1 = Generator::from_seed(alphabet.clone(), 10, 42);
+        let mut gen2 = Generator::from_seed(alphabet.clone(), 10, 42);
+        let mut gen3 = Generator::from_seed(alphabet, 10, 43);
+        let run1: Vec<_> = (0..10).map(|_| gen1.run()).collect();
+        let run2: Vec<_> = (0..10).map(|_| gen2.run()).collect();
+        let run3: Vec<_> = (0..10).map(|_| gen3.run()).collect();
+        assert_eq!(run1, run2);
+        assert_ne!(run1, run3);
+    }
+
+    #[test]
+    fn test_generator_nontrivial() {
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let mut gen = Generator::from_seed(alphabet.clone(), 6, 7).min_letters(3);
+        for _ in 0..50 {
+            let s = gen.run();
+            assert!(s.chars().filter(|x| alphabet.contains(x)).count() >= 3);
+        }
+
+        let mut gen = Generator::from_seed(alphabet.clone(), 3, 7);
+        for _ in 0..50 {
+            let regex = Regex::parse_with_alphabet(alphabet.clone(), &gen.run_nontrivial())
+                .unwrap()
+                .simplify()
+                .to_string();
+            assert_ne!(regex, "𝜀");
+            assert_ne!(regex, "∅");
+        }
+    }
+
     #[test]
     #[ignore]
     fn test_to_regex() {
@@ -536,23 +1255,66 @@ mod tests {
     }
 
     #[test]
-    fn test_simplify() {
+    fn test_derivative() {
+        let alphabet: HashSet<char> = ['0', '1', '2'].iter().copied().collect();
         let list = [
             "",
             "𝜀",
-            "𝜀𝜀((𝜀))𝜀𝜀",
-            "0|1|0|(0|1)",
-            "(0|1|2|3|𝜀)?",
-            "10|11|12|13",
-            "1𝜀2𝜀3𝜀",
-            "(1|3|4|𝜀)*",
-            "1|𝜀",
-            "1*|𝜀",
-            "1+|𝜀",
+            "0",
+            ".1*",
+            "(01|2)*0?",
+            "(0|1)+2(1|𝜀)",
+            "(0*1*)+|22",
+            "((0|𝜀)(1|𝜀))*2.",
         ];
 
-        for e in &list {
-            println!(
+        let mut words = vec![vec![]];
+        for len in 1..=4 {
+            for i in 0..3usize.pow(len) {
+                words.push(
+                    (0..len)
+                        .map(|k| ['0', '1', '2'][i / 3usize.pow(k) % 3])
+                        .collect(),
+                );
+            }
+        }
+
+        for e in &list {
+            let regex = Regex::parse_with_alphabet(alphabet.clone(), e).unwrap();
+            let nfa = regex.to_nfa();
+            if let Some(w) = words.iter().find(|x| regex.is_match(x) != nfa.run(x)) {
+                panic!("{} and its NFA disagree on {:?}", e, w);
+            }
+            if let Some(w) = words
+                .iter()
+                .find(|x| regex.derivative('0').is_match(x) != nfa.run(&[&['0'], &x[..]].concat()))
+            {
+                panic!(
+                    "the derivative of {} by 0 disagrees with its NFA on {:?}",
+                    e, w
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_simplify() {
+        let list = [
+            "",
+            "𝜀",
+            "𝜀𝜀((𝜀))𝜀𝜀",
+            "0|1|0|(0|1)",
+            "(0|1|2|3|𝜀)?",
+            "10|11|12|13",
+            "1𝜀2𝜀3𝜀",
+            "(1|3|4|𝜀)*",
+            "1|𝜀",
+            "1*|𝜀",
+            "1+|𝜀",
+        ];
+
+        for e in &list {
+            println!(
                 "{}  :  {}",
                 e,
                 Regex::parse_with_alphabet((b'0'..=b'9').map(char::from).collect(), e)
@@ -562,4 +1324,1816 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn test_parse_bounded_repetition() {
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let parse = |s: &str| Regex::parse_with_alphabet(alphabet.clone(), s);
+
+        let exact = parse("1{3}").unwrap();
+        assert!(exact.eq(&parse("111").unwrap()));
+        assert!(parse("1{2,}").unwrap().eq(&parse("111*").unwrap()));
+        assert!(parse("(12){1,3}")
+            .unwrap()
+            .eq(&parse("12|1212|121212").unwrap()));
+        assert!(parse("1{,2}").unwrap().eq(&parse("1?1?").unwrap()));
+        assert!(parse("1{12}").unwrap().eq(&parse("111111111111").unwrap()));
+
+        for s in &["1{1,4}", "(12){2,}", "1{3}", "(1|2){,2}"] {
+            let regex = parse(s).unwrap();
+            assert!(parse(&regex.to_string()).unwrap().eq(&regex));
+        }
+
+        for s in &[
+            "1{4,2}", "1{", "1{2", "1{a}", "1{}", "{2}", "1}", "1{2,3,4}",
+        ] {
+            assert!(parse(s).is_err(), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_parse_class() {
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let parse = |s: &str| Regex::parse_with_alphabet(alphabet.clone(), s);
+
+        assert_eq!(
+            parse("[0-9]").unwrap().to_string(),
+            parse("0|1|2|3|4|5|6|7|8|9").unwrap().to_string()
+        );
+        assert_eq!(
+            parse("[13-5]*").unwrap().to_string(),
+            parse("(1|3|4|5)*").unwrap().to_string()
+        );
+        assert_eq!(parse("[7]").unwrap().to_string(), "7");
+        assert!(parse("[0-2]{2}9")
+            .unwrap()
+            .eq(&parse("(0|1|2)(0|1|2)9").unwrap()));
+
+        for s in &["[9-0]", "[]", "[01", "01]", "[0(]", "[a-c]"] {
+            assert!(parse(s).is_err(), "{}", s);
+        }
+
+        let regex = "[a-z]+".parse::<Regex<char>>().unwrap();
+        assert!(regex.to_nfa().run(&['q', 'u', 'x']));
+    }
+
+    #[test]
+    fn test_parse_negated_class() {
+        let binary: HashSet<char> = ['0', '1'].iter().copied().collect();
+        assert_eq!(
+            Regex::parse_with_alphabet(binary.clone(), "[^1]")
+                .unwrap()
+                .to_string(),
+            "0"
+        );
+        assert!(Regex::parse_with_alphabet(binary, "[^01]")
+            .unwrap()
+            .to_nfa()
+            .is_empty());
+
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let parse = |s: &str| Regex::parse_with_alphabet(alphabet.clone(), s);
+        assert!(parse("[^2-9]*").unwrap().eq(&parse("[01]*").unwrap()));
+        assert!(parse("[0^]").is_err());
+        assert!("[^a]b".parse::<Regex<char>>().is_err());
+    }
+
+    #[test]
+    fn test_parse_escaped() {
+        let alphabet: HashSet<char> = ['a', '*', '.', '\\', '-', '('].iter().copied().collect();
+        let parse = |s: &str| Regex::parse_with_alphabet(alphabet.clone(), s);
+
+        let nfa = parse("a\\*\\.\\\\").unwrap().to_nfa();
+        assert!(nfa.run(&['a', '*', '.', '\\']));
+        assert!(!nfa.run(&['a', 'a', '.', '\\']));
+        assert!(parse("\\(*").unwrap().to_nfa().run(&['(', '(']));
+        assert!(parse("[a\\-\\*]").unwrap().eq(&parse("a|\\-|\\*").unwrap()));
+        assert!(parse("\\+").is_err());
+        assert!(parse("a\\").is_err());
+
+        let regex = "1\\|2".parse::<Regex<char>>().unwrap();
+        assert!(regex.to_nfa().run(&['1', '|', '2']));
+        assert!(!regex.to_nfa().run(&['1']));
+    }
+
+    #[test]
+    fn test_parse_error_position() {
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let parse = |s: &str| Regex::parse_with_alphabet(alphabet.clone(), s);
+
+        let err = parse("(12)3)45").unwrap_err();
+        assert_eq!(err.position, 5);
+        assert_eq!(err.to_string(), err.message);
+        assert_eq!(parse("(12").unwrap_err().position, 3);
+        assert_eq!(parse("12|*").unwrap_err().position, 3);
+        assert_eq!(parse("1[3-0]").unwrap_err().position, 2);
+        assert_eq!(parse("1a").unwrap_err().position, 1);
+        assert_eq!("𝜀)".parse::<Regex<char>>().unwrap_err().position, 4);
+    }
+
+    #[test]
+    fn test_parse_intersect() {
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let parse = |s: &str| Regex::parse_with_alphabet(alphabet.clone(), s).unwrap();
+
+        let even = parse("[0-9]+&(..)*");
+        assert!(even.to_nfa().run(&['1', '2']));
+        assert!(!even.to_nfa().run(&['1', '2', '3']));
+        assert!(!even.to_nfa().run(&[]));
+        assert!(even.is_match(&['1', '2', '3', '4']));
+        assert!(!even.is_match(&['1']));
+        assert!(even.eq(&parse("(..)+")));
+
+        assert!(parse("1*2|1&2").eq(&parse("1*2")));
+        assert!(parse("(12)*&1").to_nfa().is_empty());
+        assert_eq!(parse("(1&2)&3").simplify().to_string(), "1&2&3");
+        assert_eq!(parse("12&12").simplify().to_string(), "12");
+        assert_eq!(parse("(1|2)&3").simplify().to_string(), "(1|2)&3");
+
+        let regex = parse("(1|2)*&(2|3)*4");
+        assert!(parse(&regex.to_string()).eq(&regex));
+    }
+
+    #[test]
+    fn test_parse_complement() {
+        let alphabet: HashSet<char> = ['0', '1', 'a'].iter().copied().collect();
+        let parse = |s: &str| Regex::parse_with_alphabet(alphabet.clone(), s).unwrap();
+
+        let not_digits = parse("~[01]*");
+        assert!(not_digits.to_nfa().run(&['0', 'a', '1']));
+        assert!(!not_digits.to_nfa().run(&['0', '1']));
+        assert!(!not_digits.to_nfa().run(&[]));
+        assert!(not_digits.is_match(&['a']));
+        assert!(!not_digits.is_match(&['1', '0']));
+        assert!(not_digits.eq(&parse(".*a.*")));
+
+        assert!(parse("~1a").eq(&parse("(𝜀|0|a|..+)a")));
+        assert!(parse("~~(01)*").eq(&parse("(01)*")));
+        assert_eq!(parse("~~(01)*").simplify().to_string(), "(01)*");
+        assert!(parse("~(0|1)&~a").eq(&parse("𝜀|...*")));
+
+        let regex = parse("(~1)*~(0a)");
+        assert!(parse(&regex.to_string()).eq(&regex));
+        assert!(Regex::parse_with_alphabet(alphabet.clone(), "1~").is_err());
+    }
+
+    #[test]
+    fn test_literal() {
+        let alphabet: HashSet<char> = ['a', 'b', '*', '('].iter().copied().collect();
+
+        let regex = Regex::literal(alphabet.clone(), "a*(b").unwrap();
+        assert!(regex.to_nfa().run(&['a', '*', '(', 'b']));
+        assert!(!regex.to_nfa().run(&['a', 'a', '(', 'b']));
+        assert!(regex.eq(&NFA::new_matching(alphabet.clone(), &['a', '*', '(', 'b'])));
+
+        let empty = Regex::literal(alphabet.clone(), "").unwrap();
+        assert_eq!(empty.to_string(), "𝜀");
+        assert!(empty.to_nfa().run(&[]));
+        assert!(Regex::literal(alphabet, "abc").is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_serde() {
+        use rustomaton::dfa::DFA;
+
+        for (aut, _, _) in automaton_list() {
+            let json = serde_json::to_string(&aut).unwrap();
+            let nfa: NFA<char> = serde_json::from_str(&json).unwrap();
+            assert!(nfa.eq(&aut));
+
+            let dfa = aut.to_dfa();
+            let json = serde_json::to_string(&dfa).unwrap();
+            assert!(serde_json::from_str::<DFA<char>>(&json).unwrap().eq(&dfa));
+        }
+
+        let regex =
+            Regex::parse_with_alphabet(['a', 'b'].iter().copied().collect(), "(a|b)*&~a").unwrap();
+        let json = serde_json::to_string(&regex).unwrap();
+        assert!(serde_json::from_str::<Regex<char>>(&json)
+            .unwrap()
+            .eq(&regex));
+
+        let dangling =
+            r#"{"alphabet":["a"],"initials":[0],"finals":[2],"transitions":[{"a":[0]}]}"#;
+        assert!(serde_json::from_str::<NFA<char>>(dangling).is_err());
+        let dangling =
+            r#"{"alphabet":["a"],"initials":[0],"finals":[],"transitions":[{}],"epsilon":[[1]]}"#;
+        assert!(serde_json::from_str::<NFA<char>>(dangling).is_err());
+        let dangling = r#"{"alphabet":["a"],"initial":0,"finals":[],"transitions":[{"a":1}]}"#;
+        assert!(serde_json::from_str::<DFA<char>>(dangling).is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn test_json() {
+        use rustomaton::dfa::DFA;
+
+        for (aut, _, _) in automaton_list() {
+            assert!(NFA::from_json(&aut.to_json()).unwrap().eq(&aut));
+
+            let dfa = aut.to_dfa();
+            assert!(DFA::from_json(&dfa.to_json()).unwrap().eq(&dfa));
+        }
+
+        let json = automaton2().to_json();
+        for key in &[
+            "\"alphabet\"",
+            "\"initials\"",
+            "\"finals\"",
+            "\"transitions\"",
+        ] {
+            assert!(json.contains(key));
+        }
+        assert!(NFA::<char>::from_json("{\"alphabet\": []}").is_err());
+        assert!(DFA::<char>::from_json("not json").is_err());
+    }
+
+    #[test]
+    fn test_from_dot() {
+        for (aut, _, _) in automaton_list() {
+            let nfa = NFA::from_dot(&aut.to_dot()).unwrap();
+            assert!(nfa.eq(&aut));
+            assert!(aut
+                .to_dfa()
+                .eq(&NFA::from_dot(&aut.to_dfa().to_dot()).unwrap()));
+        }
+
+        let mut aut = NFA::new_empty(HashSet::new());
+        let s0 = aut.add_state();
+        let s1 = aut.add_state();
+        aut.add_initial(s0);
+        aut.add_final(s1);
+        aut.add_transition(s0, ';', s1);
+        aut.add_transition(s0, ',', s1);
+        aut.add_epsilon(s1, s0);
+        let nfa = NFA::from_dot(&aut.to_dot()).unwrap();
+        assert!(nfa.run(&[';', ',']));
+        assert!(!nfa.run(&[]));
+        assert!(nfa.eq(&aut));
+
+        let dot = "digraph {
+            node [shape = doublecircle]; S_1;
+            node [shape = point]; I_0;
+            node [shape = circle];
+            S_0 -> S_1 [label=\"a, b\"];
+            I_0 -> S_0;
+        }";
+        assert!(NFA::from_dot(dot).unwrap().run(&['b']));
+
+        for dot in &[
+            "graph { S_0; }",
+            "digraph { S_0 -> S_1; }",
+            "digraph { S_0 -> X_1 [label = \"a\"]; }",
+            "digraph { S_0 -> S_1 [label = \"ab\"]; }",
+            "digraph { S_0 -> S_1 [label = \"a]; }",
+            "digraph { I_0 -> S_1; }",
+        ] {
+            assert!(NFA::from_dot(dot).is_err(), "{}", dot);
+        }
+    }
+
+    #[test]
+    fn test_dfa_dot() {
+        let dfa = "ab*|c".parse::<NFA<char>>().unwrap().to_dfa().complete();
+        let dot = dfa.to_dot();
+        assert_eq!(dot.matches("I_").count(), 2);
+        assert_eq!(dot.matches("style = dashed").count(), 1);
+        assert!(NFA::from_dot(&dot).unwrap().eq(&dfa));
+
+        let mut nfa = NFA::new_empty(['a', 'b'].iter().cloned().collect());
+        let s0 = nfa.add_state();
+        let s1 = nfa.add_state();
+        nfa.add_initial(s0);
+        nfa.add_final(s1);
+        nfa.add_transition(s0, 'a', s1);
+        nfa.add_transition(s0, 'b', s1);
+        let dot = nfa.to_dfa().to_dot();
+        assert!(dot.contains("S_0 -> S_1 [label = \"a, b\"];"));
+        assert!(!dot.contains("dashed"));
+    }
+
+    #[test]
+    fn test_dot_compact_ranges() {
+        let opts = DotOptions {
+            compact_ranges: true,
+        };
+
+        let mut nfa = NFA::new_empty(HashSet::new());
+        let s0 = nfa.add_state();
+        let s1 = nfa.add_state();
+        let s2 = nfa.add_state();
+        nfa.add_initial(s0);
+        nfa.add_final(s2);
+        for c in (b'0'..=b'9').map(char::from) {
+            nfa.add_transition(s0, c, s1);
+        }
+        for c in &['a', 'b', 'c', 'e'] {
+            nfa.add_transition(s1, *c, s2);
+        }
+        let dot = nfa.to_dot_opts(opts);
+        assert!(dot.contains("\"0-9\""));
+        assert!(dot.contains("\"a-c, e\""));
+        assert!(!nfa.to_dot().contains("0-9"));
+        assert!(NFA::from_dot(&dot).unwrap().eq(&nfa));
+
+        let mut nfa = NFA::new_empty(HashSet::new());
+        let s0 = nfa.add_state();
+        let s1 = nfa.add_state();
+        nfa.add_initial(s0);
+        nfa.add_final(s1);
+        for x in 10..13 {
+            nfa.add_transition(s0, x, s1);
+        }
+        let dot = nfa.to_dot_opts(opts);
+        for x in 10..13 {
+            assert!(dot.contains(&x.to_string()));
+        }
+    }
+
+    #[test]
+    fn test_csv() {
+        for (aut, _, _) in automaton_list() {
+            let dfa = aut.to_dfa();
+            let csv = dfa.to_csv();
+            let back = DFA::from_csv(&csv).unwrap();
+            assert_eq!(back.to_csv(), csv);
+            assert!(back.eq(&aut));
+        }
+
+        let nfa = "ab|,".parse::<NFA<char>>().unwrap();
+        let csv = nfa.to_dfa().to_csv();
+        assert!(csv.starts_with("state,\",\",a,b\n->0,"));
+        assert!(DFA::from_csv(&csv).unwrap().eq(&nfa));
+
+        for csv in &[
+            "",
+            "name,a\n->0,0",
+            "state,a\n0,0",
+            "state,a\n->0,1",
+            "state,a\n->1,0",
+            "state,a,b\n->0,0",
+            "state,ab\n->0,0",
+        ] {
+            assert!(DFA::from_csv(csv).is_err(), "{}", csv);
+        }
+    }
+
+    #[test]
+    fn test_run_trace() {
+        for (aut, acc, rej) in automaton_list() {
+            let dfa = aut.to_dfa();
+            for w in acc.iter().chain(rej.iter()) {
+                let (trace, accepted) = dfa.run_trace(w);
+                assert_eq!(accepted, dfa.run(w));
+                assert!(trace.len() <= w.len() + 1);
+                assert!(!accepted || trace.len() == w.len() + 1);
+
+                let (trace, accepted) = aut.run_trace(w);
+                assert_eq!(accepted, aut.run(w));
+                assert!(trace.iter().all(|x| !x.is_empty()));
+            }
+        }
+
+        let nfa = "ab".parse::<NFA<char>>().unwrap();
+        let dfa = nfa.to_dfa();
+        let (trace, accepted) = dfa.run_trace(&['a', 'a', 'b']);
+        assert!(!accepted);
+        assert_eq!(trace.len(), 2);
+        let (trace, accepted) = dfa.run_trace(&['a', 'b']);
+        assert!(accepted);
+        assert_eq!(trace.len(), 3);
+        assert_eq!(trace[0], 0);
+
+        let (trace, accepted) = nfa.run_trace(&['a', 'a', 'b']);
+        assert!(!accepted);
+        assert_eq!(trace.len(), 2);
+        let (trace, accepted) = nfa.run_trace(&['a', 'b']);
+        assert!(accepted);
+        assert_eq!(trace.len(), 3);
+    }
+
+    #[test]
+    fn test_runner() {
+        for (aut, acc, rej) in automaton_list() {
+            let dfa = aut.to_dfa();
+            let mut runner = dfa.runner();
+            for w in acc.iter().chain(rej.iter()) {
+                runner.reset();
+                for l in w {
+                    runner.feed(*l);
+                }
+                assert_eq!(runner.is_accepting(), dfa.run(w));
+            }
+        }
+
+        let dfa = "ab*".parse::<NFA<char>>().unwrap().to_dfa().complete();
+        let mut runner = dfa.runner();
+        assert!(!runner.is_accepting());
+        assert!(!runner.feed('a'));
+        assert!(runner.is_accepting());
+        assert!(!runner.feed('b'));
+        assert!(runner.feed('a'));
+        assert!(!runner.is_accepting());
+        assert!(runner.state().is_some());
+        runner.reset();
+        assert!(runner.feed('b'));
+        assert!(runner.feed('a'));
+    }
+
+    #[test]
+    fn test_longest_match() {
+        for (aut, acc, rej) in automaton_list() {
+            let dfa = aut.to_dfa();
+            for w in acc.iter().chain(rej.iter()) {
+                let expected = (0..=w.len()).rev().find(|i| dfa.run(&w[..*i]));
+                assert_eq!(dfa.longest_match(w), expected);
+            }
+        }
+
+        let dfa = "a+b?".parse::<NFA<char>>().unwrap().to_dfa();
+        assert_eq!(dfa.longest_match(&['a', 'a', 'b', 'b']), Some(3));
+        assert_eq!(dfa.longest_match(&['a', 'a', 'c']), Some(2));
+        assert_eq!(dfa.longest_match(&['b', 'a']), None);
+        assert_eq!(dfa.longest_match(&[]), None);
+        let dfa = "a*".parse::<NFA<char>>().unwrap().to_dfa();
+        assert_eq!(dfa.longest_match(&['b']), Some(0));
+    }
+
+    #[test]
+    fn test_run_str() {
+        for (aut, acc, rej) in automaton_list() {
+            let dfa = aut.to_dfa();
+            for w in acc.iter().chain(rej.iter()) {
+                let s: String = w.iter().collect();
+                assert_eq!(aut.run_str(&s), aut.run(w));
+                assert_eq!(aut.is_match(&s), aut.run(w));
+                assert_eq!(dfa.run_str(&s), aut.run(w));
+                assert_eq!(dfa.is_match(&s), aut.run(w));
+            }
+        }
+
+        let nfa = "h(e|a)llo".parse::<NFA<char>>().unwrap();
+        assert!(nfa.run_str("hello"));
+        assert!(nfa.to_dfa().is_match("hallo"));
+        assert!(!nfa.run_str("hllo"));
+    }
+
+    #[test]
+    fn test_run_iter() {
+        for (aut, acc, rej) in automaton_list() {
+            let dfa = aut.to_dfa();
+            for w in acc.iter().chain(rej.iter()) {
+                assert_eq!(aut.run_iter(w.iter().cloned()), aut.run(w));
+                assert_eq!(dfa.run_iter(w.iter().cloned()), aut.run(w));
+            }
+        }
+
+        let nfa = "(ab)*".parse::<NFA<char>>().unwrap();
+        assert!(nfa.run_iter("abab".chars()));
+        assert!(nfa.to_dfa().run_iter("ab".chars().cycle().take(6)));
+        assert!(!nfa.run_iter("aba".chars()));
+    }
+
+    #[test]
+    #[cfg(feature = "rand")]
+    fn test_sample_word() {
+        let mut rng = rand::thread_rng();
+        for (aut, _, _) in automaton_list() {
+            let dfa = aut.to_dfa();
+            for len in 0..6 {
+                match dfa.sample_word(len, &mut rng) {
+                    None => assert_eq!(dfa.count_words_of_length(len), 0),
+                    Some(w) => {
+                        assert_eq!(w.len(), len);
+                        assert!(aut.run(&w));
+                    }
+                }
+            }
+        }
+
+        let dfa = "a(b|c)|d".parse::<NFA<char>>().unwrap().to_dfa();
+        let mut seen = HashMap::new();
+        for _ in 0..300 {
+            *seen
+                .entry(dfa.sample_word(2, &mut rng).unwrap())
+                .or_insert(0) += 1;
+        }
+        assert_eq!(seen.len(), 2);
+        assert!(seen.values().all(|x| *x > 100));
+        assert_eq!(dfa.sample_word(3, &mut rng), None);
+    }
+
+    #[test]
+    fn test_display_table() {
+        let mut nfa = NFA::new_empty(['a', 'b'].iter().cloned().collect());
+        let s0 = nfa.add_state();
+        let s1 = nfa.add_state();
+        nfa.add_initial(s0);
+        nfa.add_final(s1);
+        nfa.add_transition(s0, 'a', s0);
+        nfa.add_transition(s0, 'a', s1);
+        nfa.add_transition(s1, 'b', s0);
+        assert_eq!(nfa.to_string(), "   | a   | b\n>0 | 0,1 |\n*1 |     | 0\n");
+
+        nfa.add_epsilon(s1, s0);
+        assert_eq!(
+            nfa.to_string(),
+            "   | a   | b | 𝜀\n>0 | 0,1 |   |\n*1 |     | 0 | 0\n"
+        );
+
+        let dfa = "ab".parse::<NFA<char>>().unwrap().to_dfa();
+        let table = dfa.to_string();
+        assert_eq!(table.lines().count(), dfa.to_csv().lines().count());
+        assert!(table.contains(">0 |"));
+        assert_eq!(table.matches('*').count(), 1);
+    }
+
+    #[test]
+    fn test_bit_operators() {
+        let a = "a*b".parse::<NFA<char>>().unwrap();
+        let b = "ab*".parse::<NFA<char>>().unwrap();
+        let c = "c".parse::<NFA<char>>().unwrap();
+
+        let nfa = a.clone() & b.clone() | c.clone();
+        assert!(nfa.run(&['a', 'b']));
+        assert!(nfa.run(&['c']));
+        assert!(!nfa.run(&['a']));
+        assert!(!nfa.run(&['b']));
+
+        let dfa = a.to_dfa() & b.to_dfa() | c.to_dfa();
+        assert!(dfa.eq(&nfa));
+        assert!((a.to_dfa() | b.to_dfa()).eq(&(a + b)));
+    }
+
+    #[test]
+    fn test_dead_states() {
+        for (aut, acc, rej) in automaton_list() {
+            let dfa = aut.to_dfa().complete();
+            let trimmed = dfa.clone().remove_dead();
+            assert!(trimmed.dead_states().len() <= 1);
+            assert!(trimmed.eq(&dfa));
+            assert_eq!(trimmed.clone().remove_dead().to_csv(), trimmed.to_csv());
+            for w in acc {
+                assert!(trimmed.run(&w));
+            }
+            for w in rej {
+                assert!(!trimmed.run(&w));
+            }
+        }
+
+        let dfa = "ab".parse::<NFA<char>>().unwrap().to_dfa();
+        assert!(dfa.dead_states().is_empty());
+        let complete = dfa.clone().complete();
+        assert_eq!(complete.dead_states().len(), 1);
+        assert_eq!(complete.remove_dead().to_csv(), dfa.to_csv());
+    }
+
+    #[test]
+    fn test_equivalence_classes() {
+        for (aut, _, _) in automaton_list() {
+            let dfa = aut.to_dfa();
+            let classes = dfa.equivalence_classes();
+            let states: Vec<usize> = classes.iter().flatten().cloned().collect();
+            let all: HashSet<usize> = states.iter().cloned().collect();
+            assert_eq!(states.len(), all.len());
+            assert_eq!(all, (0..dfa.to_csv().lines().count() - 1).collect());
+        }
+
+        let dfa = DFA::from_csv("state,a,b\n->0,1,2\n*1,,\n*2,,\n3,3,3").unwrap();
+        let mut classes: Vec<Vec<usize>> = dfa
+            .equivalence_classes()
+            .into_iter()
+            .map(|x| {
+                let mut x: Vec<usize> = x.into_iter().collect();
+                x.sort();
+                x
+            })
+            .collect();
+        classes.sort();
+        assert_eq!(classes, vec![vec![0], vec![1, 2], vec![3]]);
+    }
+
+    #[test]
+    fn test_pumping_length() {
+        for (aut, acc, _) in automaton_list() {
+            let dfa = aut.to_dfa();
+            let p = dfa.pumping_length();
+            assert!(p >= 1);
+            assert!(p < dfa.to_csv().lines().count());
+
+            // every accepted word at least as long as p can be pumped
+            for w in acc.iter().filter(|w| w.len() >= p) {
+                let pumpable = (0..p).any(|i| {
+                    (i + 1..=p).any(|j| {
+                        (0..4).all(|k| {
+                            let mut v = w[..i].to_vec();
+                            for _ in 0..k {
+                                v.extend_from_slice(&w[i..j]);
+                            }
+                            v.extend_from_slice(&w[j..]);
+                            dfa.run(&v)
+                        })
+                    })
+                });
+                assert!(pumpable);
+            }
+        }
+
+        let dfa = "abc".parse::<NFA<char>>().unwrap().to_dfa();
+        assert_eq!(dfa.pumping_length(), 4);
+    }
+
+    #[test]
+    fn test_state_distances() {
+        let mut nfa = NFA::new_empty(['a', 'b'].iter().cloned().collect());
+        let s: Vec<usize> = (0..5).map(|_| nfa.add_state()).collect();
+        nfa.add_initial(s[0]);
+        nfa.add_transition(s[0], 'a', s[1]);
+        nfa.add_transition(s[1], 'b', s[2]);
+        nfa.add_transition(s[2], 'a', s[0]);
+        nfa.add_epsilon(s[1], s[3]);
+        nfa.add_transition(s[4], 'a', s[0]);
+        assert_eq!(
+            nfa.state_distances(),
+            vec![Some(0), Some(1), Some(2), Some(1), None]
+        );
+
+        for (aut, _, _) in automaton_list() {
+            let distances = aut.state_distances();
+            for i in aut.initials() {
+                assert_eq!(distances[*i], Some(0));
+            }
+            for (from, _, to) in aut.transitions() {
+                if let Some(d) = distances[from] {
+                    assert!(distances[to].unwrap() <= d + 1);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_full_regression() {
+        for (aut, _, _) in automaton_list() {
+            assert_eq!(aut.is_full(), aut.clone().negate().is_empty());
+            assert_eq!(aut.to_dfa().is_full(), aut.clone().negate().is_empty());
+        }
+
+        // a single initial and final state over the empty alphabet accepts every word
+        let mut nfa: NFA<char> = NFA::new_empty(HashSet::new());
+        let s0 = nfa.add_state();
+        nfa.add_initial(s0);
+        nfa.add_final(s0);
+        assert!(nfa.is_full());
+        assert!(nfa.clone().negate().is_empty());
+
+        // the same state over a non empty alphabet only accepts the empty word
+        let mut nfa: NFA<char> = NFA::new_empty(['a'].iter().cloned().collect());
+        let s0 = nfa.add_state();
+        nfa.add_initial(s0);
+        nfa.add_final(s0);
+        assert!(!nfa.is_full());
+        nfa.add_transition(s0, 'a', s0);
+        assert!(nfa.is_full());
+
+        // a non final state reachable in an NFA doesn't prevent it from being full
+        let s1 = nfa.add_state();
+        nfa.add_transition(s0, 'a', s1);
+        assert!(nfa.is_full());
+
+        // no initial state
+        let mut nfa: NFA<char> = NFA::new_empty(HashSet::new());
+        nfa.add_state();
+        assert!(!nfa.is_full());
+    }
+
+    #[test]
+    fn test_kleene_idempotent() {
+        for (aut, _, _) in automaton_list() {
+            let once = aut.clone().kleene();
+            let twice = once.clone().kleene();
+            assert!(once.eq(&twice));
+            assert_eq!(once.num_states(), twice.num_states());
+            assert!(once.run(&[]));
+            assert!(aut.clone().kleene().eq(&aut.to_dfa().kleene()));
+        }
+
+        // the initial state is already final
+        let nfa = "a*b"
+            .parse::<NFA<char>>()
+            .unwrap()
+            .unite("𝜀".parse::<NFA<char>>().unwrap());
+        let star = nfa.clone().kleene();
+        assert!(star.eq(&"(a*b)*".parse::<NFA<char>>().unwrap()));
+        assert!(star.clone().kleene().eq(&star));
+    }
+
+    #[test]
+    fn test_to_regex_ordered() {
+        for (aut, acc, rej) in automaton_list() {
+            let regex = aut.to_regex_ordered();
+            assert!(regex.eq(&aut));
+            for w in acc {
+                assert!(regex.is_match(&w));
+            }
+            for w in rej {
+                assert!(!regex.is_match(&w));
+            }
+        }
+
+        // the full closure used to produce more than 60000 characters
+        assert!(automaton7().to_regex().to_string().len() < 5000);
+    }
+
+    #[test]
+    fn test_parse_dot_with_alphabet() {
+        let alphabet: HashSet<char> = "0123".chars().collect();
+        let regex = Regex::parse_with_alphabet(alphabet.clone(), ".3").unwrap();
+        assert!(regex.uses_dot());
+        for c in alphabet.iter() {
+            assert!(regex.is_match(&[*c, '3']));
+            assert!(!regex.is_match(&['3', *c]) || *c == '3');
+        }
+        assert!(!regex.is_match(&['3']));
+        assert!(Regex::parse_with_alphabet(alphabet.clone(), ".4").is_err());
+
+        assert!(!Regex::parse_with_alphabet(alphabet, "(0|1)*3")
+            .unwrap()
+            .uses_dot());
+        assert!("~(.a)".parse::<Regex<char>>().unwrap().uses_dot());
+    }
+
+    #[test]
+    fn test_simplify_suffix() {
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let parse = |s: &str| Regex::parse_with_alphabet(alphabet.clone(), s).unwrap();
+
+        assert_eq!(parse("10|20|30").simplify().to_string(), "(1|2|3)0");
+        assert_eq!(parse("01|02|03").simplify().to_string(), "0(1|2|3)");
+        assert_eq!(parse("0|10").simplify().to_string(), "1?0");
+
+        // a common prefix and a common suffix
+        let regex = parse("102|112|122");
+        let simplified = regex.clone().simplify();
+        assert_eq!(simplified.to_string(), "1(0|1|2)2");
+        assert!(simplified.eq(&regex));
+
+        for s in &["12|32|3|2", "(1|2)0|30*0", "1*0|0", "123|23|3"] {
+            let regex = parse(s);
+            assert!(regex.clone().simplify().eq(&regex), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_star_height() {
+        let height = |s: &str| s.parse::<Regex<char>>().unwrap().star_height();
+        assert_eq!(height("(a*b)*"), 2);
+        assert_eq!(height("a*|b*"), 1);
+        assert_eq!(height("ab|c"), 0);
+        assert_eq!(height("(a+b{2,})*"), 2);
+        assert_eq!(height("(a*){2,3}"), 1);
+        assert_eq!(height("(ab?){1,4}"), 0);
+        assert_eq!(height("~(a*)&b"), 1);
+    }
+
+    #[test]
+    fn test_compiled_regex() {
+        let regex = "(ab|c)*d".parse::<Regex<char>>().unwrap();
+        let compiled = regex.clone().compile();
+        assert!(compiled.run_str("ababcd"));
+        assert!(!compiled.run_str("abab"));
+
+        for s in &["d", "abd", "cccd", "abcabd", "", "ab", "abcdd", "bad"] {
+            let w: Vec<char> = s.chars().collect();
+            assert_eq!(compiled.run_str(s), regex.to_nfa().run_str(s), "{}", s);
+            assert_eq!(compiled.is_match(&w), regex.is_match(&w), "{}", s);
+        }
+        assert!(compiled.dfa().eq(&regex));
+        assert!(compiled.dfa().eq(&regex.to_dfa()));
+        assert_eq!(compiled.shortest_accepted(), Some(vec!['d']));
+
+        let smaller = "abd|cd".parse::<Regex<char>>().unwrap().compile();
+        let other = "(ab|c|e)*d".parse::<Regex<char>>().unwrap().compile();
+        assert!(compiled.contains(&smaller));
+        assert!(!smaller.contains(&compiled));
+        assert!(compiled.contains(&compiled));
+        assert!(other.contains(&compiled));
+        assert!(!compiled.contains(&other));
+
+        let empty = NFA::new_empty(HashSet::new()).to_regex().compile();
+        assert_eq!(empty.shortest_accepted(), None);
+        assert!(compiled.contains(&empty));
+        assert!(!empty.contains(&compiled));
+
+        for (aut, _, _) in automaton_list() {
+            let regex = aut.to_regex().compile();
+            let reference = aut.to_dfa();
+            assert!(regex.dfa().eq(&reference));
+            assert!(regex.contains(&regex));
+        }
+    }
+
+    #[test]
+    fn test_accepts_suffix() {
+        for (aut, acc, rej) in automaton_list() {
+            let dfa = aut.to_dfa();
+            for w in acc.iter().chain(rej.iter()) {
+                let suffix = (0..=w.len()).any(|i| dfa.run(&w[i..]));
+                let factor = (0..=w.len()).any(|i| (i..=w.len()).any(|j| dfa.run(&w[i..j])));
+                assert_eq!(dfa.accepts_suffix(w), suffix);
+                assert_eq!(dfa.accepts_substring(w), factor);
+            }
+        }
+
+        let dfa = "ab".parse::<NFA<char>>().unwrap().to_dfa();
+        assert!(dfa.accepts_suffix(&['b', 'a', 'b']));
+        assert!(!dfa.accepts_suffix(&['a', 'b', 'a']));
+        assert!(dfa.accepts_substring(&['a', 'b', 'a']));
+        assert!(!dfa.accepts_substring(&['b', 'a']));
+    }
+
+    #[test]
+    fn test_from_raw_duplicates() {
+        let mut map = HashMap::new();
+        map.insert('a', vec![1, 1, 0, 1]);
+        let nfa = NFA::from_raw(
+            ['a'].iter().cloned().collect(),
+            [0].iter().cloned().collect(),
+            [1].iter().cloned().collect(),
+            vec![map, HashMap::new()],
+        )
+        .unwrap();
+
+        let mut transitions: Vec<_> = nfa.transitions().collect();
+        transitions.sort();
+        assert_eq!(transitions, vec![(0, 'a', 0), (0, 'a', 1)]);
+        assert!(nfa.run(&['a', 'a']));
+    }
+
+    #[test]
+    fn test_minimize_with_map() {
+        for (aut, _, _) in automaton_list() {
+            let dfa = aut.to_dfa();
+            let (min, map) = dfa.clone().minimize_with_map();
+            assert!(min.eq(&dfa));
+            assert_eq!(map.len(), dfa.to_csv().lines().count() - 1);
+            assert_eq!(
+                min.to_csv().lines().count(),
+                dfa.clone().minimize_hopcroft().to_csv().lines().count()
+            );
+
+            // the map is a morphism from `dfa` to `min`
+            let (dfa_trace, _) = dfa.run_trace(&[]);
+            let (min_trace, _) = min.run_trace(&[]);
+            assert_eq!(map[dfa_trace[0]], min_trace[0]);
+            let dfa_table = dfa.to_csv();
+            let min_table = min.to_csv();
+            let rows = |table: &str| -> Vec<Vec<String>> {
+                table
+                    .lines()
+                    .skip(1)
+                    .map(|x| x.split(',').skip(1).map(|x| x.to_string()).collect())
+                    .collect()
+            };
+            let (dfa_rows, min_rows) = (rows(&dfa_table), rows(&min_table));
+            for (i, row) in dfa_rows.iter().enumerate() {
+                for (j, t) in row.iter().enumerate() {
+                    if let Ok(t) = t.parse::<usize>() {
+                        assert_eq!(min_rows[map[i]][j], map[t].to_string());
+                    }
+                }
+            }
+        }
+
+        let dfa = DFA::from_csv("state,a\n->0,1\n*1,2\n*2,2").unwrap();
+        let (min, map) = dfa.minimize_with_map();
+        assert_eq!(map[1], map[2]);
+        assert_ne!(map[0], map[1]);
+        assert_eq!(min.to_csv().lines().count(), 3);
+    }
+
+    #[test]
+    fn test_product() {
+        let list = automaton_list();
+        for (a, _, _) in &list {
+            for (b, _, _) in &list {
+                let inter = a.clone().product(b, |x, y| x && y);
+                assert!(inter.eq(&a.clone().intersect(b.clone())));
+
+                // complete and deterministic automata over the same alphabet
+                let mut alphabet = a.alphabet().clone();
+                alphabet.extend(b.alphabet().iter().cloned());
+                let da = a.to_dfa().complement_over(&alphabet).negate().to_nfa();
+                let db = b.to_dfa().complement_over(&alphabet).negate().to_nfa();
+                assert!(da
+                    .clone()
+                    .product(&db, |x, y| x && !y)
+                    .eq(&a.clone().difference(b.clone())));
+                assert!(da
+                    .product(&db, |x, y| x != y)
+                    .eq(&a.clone().symmetric_difference(b.clone())));
+            }
+        }
+    }
+
+    #[test]
+    fn test_is_disjoint() {
+        let list = automaton_list();
+        for (a, _, _) in &list {
+            for (b, _, _) in &list {
+                let inter = a.clone().intersect(b.clone());
+                assert_eq!(a.is_disjoint(b), inter.is_empty());
+                match a.intersection_witness(b) {
+                    Some(w) => {
+                        assert!(a.run(&w) && b.run(&w));
+                        assert_eq!(Some(w.len()), inter.shortest_accepted().map(|x| x.len()));
+                    }
+                    None => assert!(inter.is_empty()),
+                }
+            }
+        }
+
+        let a = "a(b|c)*".parse::<NFA<char>>().unwrap();
+        let b = "(a|b)c+".parse::<NFA<char>>().unwrap();
+        let c = "b*".parse::<NFA<char>>().unwrap();
+        assert!(!a.is_disjoint(&b));
+        assert_eq!(a.intersection_witness(&b), Some(vec!['a', 'c']));
+        assert!(a.is_disjoint(&c));
+        assert_eq!(a.intersection_witness(&c), None);
+    }
+
+    #[test]
+    fn test_is_universal_over() {
+        for (aut, _, _) in automaton_list() {
+            let dfa = aut.to_dfa();
+            assert_eq!(dfa.is_universal_over(aut.alphabet()), aut.is_full());
+        }
+
+        let dfa = "(a|b)*".parse::<NFA<char>>().unwrap().to_dfa();
+        let ab: HashSet<char> = ['a', 'b'].iter().cloned().collect();
+        let abc: HashSet<char> = ['a', 'b', 'c'].iter().cloned().collect();
+        let a: HashSet<char> = ['a'].iter().cloned().collect();
+        assert!(dfa.is_full());
+        assert!(dfa.is_universal_over(&ab));
+        assert!(dfa.is_universal_over(&a));
+        assert!(!dfa.is_universal_over(&abc));
+        assert!(dfa.is_universal_over(&HashSet::new()));
+
+        let dfa = "a*|b".parse::<NFA<char>>().unwrap().to_dfa();
+        assert!(dfa.is_universal_over(&a));
+        assert!(!dfa.is_universal_over(&ab));
+    }
+
+    #[test]
+    fn test_string_letters() {
+        let words = ["if", "then", "else"];
+        let mut nfa: NFA<String> = NFA::new_empty(words.iter().map(|x| x.to_string()).collect());
+        for _ in 0..3 {
+            nfa.add_state();
+        }
+        nfa.add_initial(0);
+        nfa.add_final(2);
+        nfa.add_transition(0, "if".to_string(), 1);
+        nfa.add_transition(1, "then".to_string(), 2);
+        nfa.add_transition(2, "else".to_string(), 0);
+
+        let word = |w: &[&str]| w.iter().map(|x| x.to_string()).collect::<Vec<String>>();
+        assert!(nfa.run(&word(&["if", "then"])));
+        assert!(nfa.run(&word(&["if", "then", "else", "if", "then"])));
+        assert!(!nfa.run(&word(&["if", "else"])));
+
+        let dfa = nfa.clone().to_dfa().minimize();
+        assert!(dfa.run(&word(&["if", "then"])));
+        assert!(!dfa.run(&word(&["then"])));
+        assert!(nfa.clone().eq(&dfa.to_nfa()));
+        assert_eq!(nfa.shortest_accepted(), Some(word(&["if", "then"])));
+    }
+
+    #[test]
+    fn test_from_words() {
+        let alphabet: HashSet<char> = ['a', 'b', 'c'].iter().cloned().collect();
+        let words: Vec<Vec<char>> = ["abc", "ab", "b", "abc", "ca"]
+            .iter()
+            .map(|x| x.chars().collect())
+            .collect();
+        let nfa = NFA::from_words(alphabet.clone(), &words);
+        for word in &words {
+            assert!(nfa.run(word));
+        }
+        for word in ["", "a", "c", "abcc", "bb", "ac"].iter() {
+            assert!(!nfa.run(&word.chars().collect::<Vec<_>>()));
+        }
+
+        let union = words
+            .iter()
+            .fold(NFA::new_empty(alphabet.clone()), |acc, w| {
+                acc.unite(NFA::new_matching(alphabet.clone(), w))
+            });
+        let minimal = nfa.to_dfa().minimize();
+        assert!(minimal.eq(&union.to_dfa().minimize()));
+
+        let empty = NFA::from_words(alphabet.clone(), &[]);
+        assert!(empty.is_empty());
+        let epsilon = NFA::from_words(alphabet.clone(), &[Vec::new()]);
+        assert!(epsilon.eq(&NFA::new_empty_word(alphabet)));
+    }
+
+    #[test]
+    fn test_collect_words() {
+        let aut: NFA<char> = vec!["ab", "b", "ba"]
+            .into_iter()
+            .map(|x| x.chars().collect())
+            .collect();
+        let alphabet: HashSet<char> = ['a', 'b'].iter().cloned().collect();
+        assert_eq!(aut.alphabet(), &alphabet);
+        assert!(aut.run(&['a', 'b']));
+        assert!(aut.run(&['b']));
+        assert!(aut.run(&['b', 'a']));
+        assert!(!aut.run(&['a']));
+        assert!(!aut.run(&[]));
+
+        let empty: NFA<char> = Vec::<Vec<char>>::new().into_iter().collect();
+        assert!(empty.is_empty());
+        assert!(empty.alphabet().is_empty());
+    }
+
+    #[test]
+    fn test_restrict_alphabet() {
+        let alphabet: HashSet<char> = ['0', '1', '2'].iter().cloned().collect();
+        let keep: HashSet<char> = ['0', '1'].iter().cloned().collect();
+        let full = NFA::new_full(alphabet.clone()).concatenate(NFA::new_length(alphabet, 2));
+        let restricted = full.clone().restrict_alphabet(&keep);
+        assert_eq!(restricted.alphabet(), &keep);
+        assert!(restricted.run(&['0', '1', '1']));
+        assert!(!restricted.run(&['0']));
+        for word in &[&['2', '2'][..], &['0', '2'][..], &['2', '1', '0'][..]] {
+            assert!(full.run(word));
+            assert!(!restricted.run(word));
+        }
+
+        let aut = Regex::parse_with_alphabet(['0', '1', '2'].iter().cloned().collect(), "02*1|1")
+            .unwrap()
+            .to_nfa();
+        let restricted = aut.restrict_alphabet(&keep).trim();
+        assert!(restricted.is_trimmed());
+        assert!(restricted.eq(&NFA::from_words(keep, &[vec!['0', '1'], vec!['1']])));
+    }
+
+    #[test]
+    fn test_shrink_alphabet() {
+        let alphabet: HashSet<char> = ['a', 'b', 'c'].iter().cloned().collect();
+        let used: HashSet<char> = ['a', 'b'].iter().cloned().collect();
+        let aut = Regex::parse_with_alphabet(alphabet.clone(), "a*b")
+            .unwrap()
+            .to_nfa();
+        assert_eq!(aut.alphabet(), &alphabet);
+        assert_eq!(aut.used_alphabet(), used);
+
+        let shrunk = aut.clone().shrink_alphabet();
+        assert_eq!(shrunk.alphabet(), &used);
+        assert!(shrunk.eq(&"a*b".parse::<NFA<char>>().unwrap()));
+        assert!(shrunk.run(&['a', 'a', 'b']));
+        assert!(!shrunk.run(&['c']));
+
+        let full = NFA::new_full(alphabet.clone());
+        assert!(full.clone().shrink_alphabet().is_full());
+        assert!(NFA::new_empty_word(alphabet).used_alphabet().is_empty());
+
+        for (aut, _, _) in automaton_list() {
+            let used = aut.used_alphabet();
+            assert!(used.is_subset(aut.alphabet()));
+            assert_eq!(aut.clone().shrink_alphabet().alphabet(), &used);
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    fn big_nfa() -> NFA<char> {
+        let words: Vec<Vec<char>> = (0..60)
+            .map(|i| format!("{:05}", i * 7919 % 100_000).chars().collect())
+            .collect();
+        NFA::from_words(HashSet::new(), &words).reverse().kleene()
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_to_dfa_parallel() {
+        let aut = big_nfa();
+        assert!(aut.num_states() >= 200);
+        let parallel = aut.to_dfa_parallel();
+        assert!(parallel.eq(&aut.to_dfa()));
+        assert!(parallel.eq(&aut));
+
+        for (aut, _, _) in automaton_list() {
+            assert!(aut.to_dfa_parallel().eq(&aut));
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[ignore]
+    #[test]
+    fn bench_to_dfa_parallel() {
+        use std::time::Instant;
+
+        let aut = big_nfa();
+        let start = Instant::now();
+        for _ in 0..10 {
+            aut.to_dfa();
+        }
+        println!("serial: {:?}", start.elapsed() / 10);
+
+        let start = Instant::now();
+        for _ in 0..10 {
+            aut.to_dfa_parallel();
+        }
+        println!("parallel: {:?}", start.elapsed() / 10);
+    }
+
+    #[test]
+    fn test_big_to_dfa() {
+        // the subsets reached in copies of the same automaton mirror those of a single copy
+        let aut = automaton7();
+        let mut scaled = aut.clone();
+        while scaled.num_states() < 128 {
+            scaled = scaled.unite(aut.clone());
+        }
+        let small = aut.to_dfa();
+        let big = scaled.to_dfa();
+        assert_eq!(big.to_csv().lines().count(), small.to_csv().lines().count());
+        assert!(big.eq(&small));
+        for word in automaton7_accept() {
+            assert!(big.run(&word));
+        }
+        for word in automaton7_reject() {
+            assert!(!big.run(&word));
+        }
+    }
+
+    #[ignore]
+    #[test]
+    fn bench_unite_many() {
+        use std::time::Instant;
+
+        let alphabet: HashSet<char> = (b'0'..=b'9').map(char::from).collect();
+        let automata: Vec<NFA<char>> = (0..1000)
+            .map(|i| {
+                let word: Vec<char> = format!("{}", i).chars().collect();
+                NFA::new_matching(alphabet.clone(), &word)
+            })
+            .collect();
+
+        let start = Instant::now();
+        let union = automata
+            .into_iter()
+            .fold(NFA::new_empty(alphabet), |acc, x| acc.unite(x));
+        println!("unite: {:?}", start.elapsed());
+        assert!(union.run(&['4', '2']));
+    }
+
+    #[test]
+    fn test_trim_noop() {
+        let structure = |aut: &NFA<char>| {
+            let mut t: Vec<_> = aut.transitions().collect();
+            t.sort();
+            (
+                aut.num_states(),
+                t,
+                aut.initials().clone(),
+                aut.finals().clone(),
+            )
+        };
+
+        for (aut, acc, rej) in automaton_list() {
+            assert_eq!(aut.is_coreachable(), aut.clone().reverse().is_reachable());
+            for dfa in vec![aut.to_dfa(), aut.to_dfa().complete()] {
+                assert_eq!(dfa.is_coreachable(), dfa.to_nfa().is_coreachable());
+                assert_eq!(dfa.is_trimmed(), dfa.to_nfa().is_trimmed());
+            }
+
+            let trimmed = aut.trim();
+            assert!(trimmed.is_trimmed());
+            let again = trimmed.clone().trim();
+            assert_eq!(structure(&again), structure(&trimmed));
+            for w in acc {
+                assert!(again.run(&w));
+            }
+            for w in rej {
+                assert!(!again.run(&w));
+            }
+        }
+
+        let mut aut = NFA::new_empty(['a'].iter().cloned().collect());
+        let s0 = aut.add_state();
+        let s1 = aut.add_state();
+        aut.add_state();
+        aut.add_initial(s0);
+        aut.add_final(s1);
+        aut.add_transition(s0, 'a', s1);
+        assert!(!aut.is_trimmed());
+        assert_eq!(aut.trim().num_states(), 2);
+    }
+
+    #[test]
+    fn test_minimize_unreachable() {
+        // an odd number of 'a', followed by 100 unreachable states leading back to it
+        let mut transitions: Vec<HashMap<char, usize>> = vec![
+            vec![('a', 1), ('b', 0)].into_iter().collect(),
+            vec![('a', 0), ('b', 1)].into_iter().collect(),
+        ];
+        for i in 2..102 {
+            let next = if i == 101 { 0 } else { i + 1 };
+            transitions.push(vec![('a', next), ('b', i % 2)].into_iter().collect());
+        }
+        let finals: HashSet<usize> = (1..102).step_by(2).collect();
+        let dfa =
+            DFA::from_raw(['a', 'b'].iter().cloned().collect(), 0, finals, transitions).unwrap();
+
+        let minimized = dfa.clone().minimize();
+        assert_eq!(minimized.to_csv().lines().count() - 1, 2);
+        assert_eq!(
+            dfa.clone().minimize_hopcroft().to_csv().lines().count() - 1,
+            2
+        );
+        assert!(minimized.eq(&dfa));
+        assert!(minimized.run(&['a', 'b', 'a', 'a']));
+        assert!(!minimized.run(&['a', 'b', 'a']));
+    }
+
+    #[test]
+    fn test_is_minimal() {
+        for (aut, _, _) in automaton_list() {
+            let dfa = aut.to_dfa();
+            let minimal = dfa.clone().minimize_hopcroft();
+            assert!(minimal.is_minimal());
+            assert_eq!(
+                dfa.is_minimal(),
+                dfa.to_csv().lines().count() == minimal.to_csv().lines().count()
+                    && dfa.is_complete()
+                    && dfa.is_reachable()
+            );
+        }
+
+        let dfa = "(a|b)*b".parse::<NFA<char>>().unwrap().to_dfa();
+        let minimal = dfa.clone().minimize_hopcroft();
+        assert!(minimal.is_minimal());
+        // not complete once its sink is removed
+        let sink = "ab"
+            .parse::<NFA<char>>()
+            .unwrap()
+            .to_dfa()
+            .minimize_hopcroft();
+        assert!(sink.is_minimal());
+        assert_eq!(sink.dead_states().len(), 1);
+        assert!(!sink.remove_dead().is_minimal());
+        // two equivalent states
+        let redundant = "ab|bb|(a|b)(a|b)(a|b)*b"
+            .parse::<NFA<char>>()
+            .unwrap()
+            .to_dfa()
+            .complete();
+        assert!(redundant.eq(&"(a|b)(a|b)*b".parse::<NFA<char>>().unwrap()));
+        assert!(!redundant.is_minimal());
+        assert!(redundant.minimize_hopcroft().is_minimal());
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #![proptest_config(proptest::prelude::ProptestConfig::with_cases(64))]
+
+        #[test]
+        fn test_arbitrary_nfa(aut in proptest::prelude::any::<NFA<char>>()) {
+            assert!(aut.num_states() >= 1 && aut.num_states() <= 8);
+            assert!(aut.transitions().all(|(from, _, to)| from < aut.num_states() && to < aut.num_states()));
+            assert!(aut.to_dfa().eq(&aut));
+            assert!(aut.clone().trim().eq(&aut));
+            assert!(aut.clone().reverse().reverse().eq(&aut));
+        }
+    }
+
+    #[test]
+    #[allow(clippy::reversed_empty_ranges)]
+    fn test_repeat_empty_ranges() {
+        let alphabet: HashSet<char> = ['a', 'b'].iter().cloned().collect();
+        let aut = "ab|b".parse::<NFA<char>>().unwrap();
+        let regex = "ab|b".parse::<Regex<char>>().unwrap();
+        let empty = NFA::new_empty(alphabet);
+
+        for (nfa, reg) in [
+            (aut.clone().repeat(0..0), regex.clone().repeat(0..0)),
+            (aut.clone().repeat(..0), regex.clone().repeat(..0)),
+            (aut.clone().repeat(3..2), regex.clone().repeat(3..2)),
+            (aut.clone().repeat(5..=3), regex.clone().repeat(5..=3)),
+            (
+                aut.clone().repeat((Excluded(usize::MAX), Unbounded)),
+                regex.clone().repeat((Excluded(usize::MAX), Unbounded)),
+            ),
+        ]
+        .iter()
+        {
+            assert!(nfa.is_empty());
+            assert!(nfa.eq(&empty));
+            assert!(reg.to_nfa().is_empty());
+            assert!(reg.eq(&empty));
+        }
+
+        assert!(aut
+            .clone()
+            .repeat(..1)
+            .eq(&NFA::new_empty_word(aut.alphabet().clone())));
+        assert!(aut.clone().repeat(0..=0).run(&[]));
+        assert!(aut.clone().at_most(0).run(&[]));
+        assert!(aut.clone().at_least(0).eq(&aut.clone().kleene()));
+        assert!(regex.clone().repeat(2..3).eq(&aut.clone().repeat(2..=2)));
+    }
+
+    #[test]
+    fn test_new_length_zero() {
+        let alphabet: HashSet<char> = ['a', 'b'].iter().cloned().collect();
+        let structure = |aut: &NFA<char>| {
+            let mut t: Vec<_> = aut.transitions().collect();
+            t.sort();
+            let mut e: Vec<_> = aut.epsilon_transitions().collect();
+            e.sort();
+            (
+                aut.num_states(),
+                t,
+                e,
+                aut.initials().clone(),
+                aut.finals().clone(),
+            )
+        };
+
+        let zero = NFA::new_length(alphabet.clone(), 0);
+        let epsilon = NFA::new_empty_word(alphabet.clone());
+        assert_eq!(structure(&zero), structure(&epsilon));
+        assert_eq!(zero.alphabet(), epsilon.alphabet());
+        assert!(zero.eq(&epsilon));
+        assert!(zero.run(&[]));
+        assert!(!zero.run(&['a']));
+
+        for (aut, acc, rej) in automaton_list() {
+            let left = NFA::new_length(aut.alphabet().clone(), 0).concatenate(aut.clone());
+            let right = aut
+                .clone()
+                .concatenate(NFA::new_length(aut.alphabet().clone(), 0));
+            assert!(left.eq(&aut));
+            assert!(right.eq(&aut));
+            for w in &acc {
+                assert!(left.run(w) && right.run(w));
+            }
+            for w in &rej {
+                assert!(!left.run(w) && !right.run(w));
+            }
+        }
+
+        let from_regex = Regex::parse_with_alphabet(alphabet, "𝜀").unwrap().to_nfa();
+        assert!(from_regex.eq(&epsilon));
+    }
+
+    #[test]
+    fn test_union_concat_all() {
+        let alphabet: HashSet<char> = ['a', 'b', 'c'].iter().cloned().collect();
+        let parts: Vec<NFA<char>> = ["a*", "b|c", "ab"]
+            .iter()
+            .map(|x| {
+                Regex::parse_with_alphabet(alphabet.clone(), x)
+                    .unwrap()
+                    .to_nfa()
+            })
+            .collect();
+
+        let union = NFA::union_all(alphabet.clone(), parts.clone());
+        let folded = parts
+            .iter()
+            .cloned()
+            .fold(NFA::new_empty(alphabet.clone()), |acc, x| acc.unite(x));
+        assert!(union.eq(&folded));
+        assert_eq!(
+            union.num_states(),
+            parts.iter().map(|x| x.num_states()).sum::<usize>()
+        );
+
+        let concat = NFA::concat_all(alphabet.clone(), parts.clone());
+        let expected = Regex::parse_with_alphabet(alphabet.clone(), "a*(b|c)ab").unwrap();
+        assert!(concat.eq(&expected));
+        assert!(concat.run(&['a', 'a', 'c', 'a', 'b']));
+        assert!(!concat.run(&['a', 'b']));
+
+        let none: Vec<NFA<char>> = Vec::new();
+        assert!(NFA::union_all(alphabet.clone(), none.clone()).is_empty());
+        assert!(NFA::concat_all(alphabet.clone(), none).eq(&NFA::new_empty_word(alphabet)));
+    }
+
+    #[test]
+    fn test_to_dfa_with_stats() {
+        let n = 4;
+        let aut = nth_letter_from_end(n);
+
+        let (dfa, stats) = aut.to_dfa_with_stats();
+        assert_eq!(stats.subsets, 1 << n);
+        assert_eq!(stats.max_subset_size, n + 1);
+        assert_eq!(dfa.to_csv().lines().count() - 1, stats.subsets);
+        assert!(dfa.eq(&aut));
+
+        for (aut, _, _) in automaton_list() {
+            let (dfa, stats) = aut.to_dfa_with_stats();
+            assert!(dfa.eq(&aut));
+            assert!(stats.max_subset_size <= aut.num_states());
+            assert_eq!(dfa.to_csv().lines().count() - 1, stats.subsets);
+        }
+    }
+
+    #[test]
+    fn test_try_to_dfa() {
+        let n = 10;
+        let aut = nth_letter_from_end(n);
+
+        assert_eq!(aut.try_to_dfa(0).err(), Some(0));
+        assert_eq!(aut.try_to_dfa(100).err(), Some(100));
+        assert_eq!(aut.try_to_dfa((1 << n) - 1).err(), Some((1 << n) - 1));
+        let dfa = aut.try_to_dfa(1 << n).unwrap();
+        assert_eq!(dfa.to_csv().lines().count() - 1, 1 << n);
+        assert!(dfa.eq(&aut));
+
+        for (aut, _, _) in automaton_list() {
+            let dfa = aut.try_to_dfa(usize::MAX).unwrap();
+            assert!(dfa.eq(&aut));
+        }
+    }
+
+    #[test]
+    fn test_simplify_adjacent_repeats() {
+        let alphabet: HashSet<char> = ['a', 'b', 'c'].iter().cloned().collect();
+        let simplify = |s: &str| {
+            Regex::parse_with_alphabet(alphabet.clone(), s)
+                .unwrap()
+                .simplify()
+                .to_string()
+        };
+
+        assert_eq!(simplify("(ab)*(ab)*"), "(ab)*");
+        assert_eq!(simplify("(ab)+(ab)*"), "(ab)+");
+        assert_eq!(simplify("(ab)*(ab)+"), "(ab)+");
+        assert_eq!(simplify("(ab)+(ab)+"), "(ab){2,}");
+        assert_eq!(simplify("a*a*a*"), "a*");
+        assert_eq!(simplify("ca*a*c"), "ca*c");
+        // the inner expressions must be equal
+        assert_eq!(simplify("a*b*"), "a*b*");
+        assert_eq!(simplify("(ab)*(ba)*"), "(ab)*(ba)*");
+
+        for s in &[
+            "(ab)*(ab)*",
+            "(ab)+(ab)*",
+            "(ab)*(ab)+",
+            "(ab)+(ab)+",
+            "ca*a+c",
+        ] {
+            let regex = Regex::parse_with_alphabet(alphabet.clone(), s).unwrap();
+            assert!(regex.clone().simplify().eq(&regex), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_simplify_star_redundancies() {
+        let alphabet: HashSet<char> = ['0', '1', '2'].iter().cloned().collect();
+        let cases = [
+            ("(0|𝜀)*", "0*"),
+            ("(0*)*", "0*"),
+            ("(0?)*", "0*"),
+            ("(0+)*", "0*"),
+            ("(0*|1)*", "(0|1)*"),
+            ("(0+|1?)*", "(0|1)*"),
+            ("(0|𝜀){2,}", "0*"),
+            ("(0?1?)+", "(0?1?)*"),
+            ("(0|1)*", "(0|1)*"),
+            ("(01)+", "(01)+"),
+        ];
+        for (s, expected) in cases.iter() {
+            let regex = Regex::parse_with_alphabet(alphabet.clone(), s).unwrap();
+            let simplified = regex.clone().simplify();
+            assert_eq!(&simplified.to_string(), expected, "{}", s);
+            assert!(simplified.eq(&regex), "{}", s);
+        }
+
+        let mut gen = Generator::from_seed(alphabet.clone(), 6, 7);
+        for _ in 0..50 {
+            let s = gen.run();
+            let regex = Regex::parse_with_alphabet(alphabet.clone(), &s).unwrap();
+            assert!(regex.clone().simplify().eq(&regex), "{}", s);
+        }
+    }
+
+    #[test]
+    fn test_equivalence_witness() {
+        let alphabet: HashSet<char> = ['a', 'b'].iter().cloned().collect();
+        let parse = |s: &str| Regex::parse_with_alphabet(alphabet.clone(), s).unwrap();
+
+        assert_eq!(
+            parse("(a|b)*").equivalence_witness(&parse("(a*b*)*")),
+            Ok(())
+        );
+        assert_eq!(
+            parse("a(ba)*").equivalence_witness(&parse("(ab)*a")),
+            Ok(())
+        );
+        assert_eq!(parse("a*").equivalence_witness(&parse("a+")), Err(vec![]));
+        assert_eq!(parse("a+").equivalence_witness(&parse("a*")), Err(vec![]));
+        assert_eq!(
+            parse("ab|ba").equivalence_witness(&parse("ab")),
+            Err(vec!['b', 'a'])
+        );
+        assert_eq!(
+            parse("(a|b)*b").equivalence_witness(&parse("(a|b)*bb")),
+            Err(vec!['b'])
+        );
+
+        for (aut, _, _) in automaton_list() {
+            let regex = aut.to_regex();
+            assert_eq!(regex.equivalence_witness(&regex.clone().simplify()), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_kleene_plus_optional() {
+        let alphabet: HashSet<char> = ['a', 'b'].iter().cloned().collect();
+        let parse = |s: &str| Regex::parse_with_alphabet(alphabet.clone(), s).unwrap();
+
+        let regex = parse("ab|b");
+        assert!(regex.clone().kleene_plus().eq(&parse("(ab|b)+")));
+        assert!(regex.clone().optional().eq(&parse("(ab|b)?")));
+        assert_eq!(regex.clone().kleene_plus().to_string(), "(ab|b)+");
+        assert_eq!(regex.clone().optional().to_string(), "(ab|b)?");
+
+        let nfa = regex.to_nfa();
+        assert!(nfa.clone().kleene_plus().eq(&parse("(ab|b)+")));
+        assert!(nfa.clone().optional().eq(&parse("(ab|b)?")));
+        assert!(!nfa.clone().kleene_plus().run(&[]));
+        assert!(nfa.clone().optional().run(&[]));
+        assert!(!nfa.clone().optional().run(&['b', 'b']));
+
+        let dfa = nfa.to_dfa();
+        assert!(dfa.clone().kleene_plus().eq(&parse("(ab|b)+")));
+        assert!(dfa.optional().eq(&parse("(ab|b)?")));
+    }
+
+    #[test]
+    fn test_parse_ascii_epsilon_empty() {
+        let alphabet: HashSet<char> = ['a', 'b', 'e', '0'].iter().cloned().collect();
+        let parse = |s: &str| Regex::parse_with_alphabet(alphabet.clone(), s).unwrap();
+
+        for (ascii, unicode) in &[
+            ("\\e", "𝜀"),
+            ("a\\e|b", "a𝜀|b"),
+            ("(a|\\e)*", "(a|𝜀)*"),
+            ("\\0", "∅"),
+            ("a\\0|b", "a∅|b"),
+            ("e|0", "e|0"),
+        ] {
+            assert_eq!(parse(ascii).to_string(), parse(unicode).to_string());
+            assert!(parse(ascii).eq(&parse(unicode)));
+        }
+
+        assert!(parse("\\e").compile().run_str(""));
+        assert!(parse("\\0").to_nfa().is_empty());
+        assert!(parse("∅").to_nfa().is_empty());
+        assert!(parse("e").compile().run_str("e"));
+        assert!(!parse("\\e").compile().run_str("e"));
+        assert!(parse("0").compile().run_str("0"));
+
+        // the displayed empty set is parsed back
+        let empty = parse("a\\0");
+        assert!(parse(&empty.to_string()).eq(&empty));
+    }
+
+    #[test]
+    fn test_to_parseable_string() {
+        for (aut, _, _) in automaton_list() {
+            for regex in [aut.to_regex(), aut.to_regex().simplify()].iter() {
+                let s = regex.to_parseable_string();
+                assert!(!s.contains('.'), "{}", s);
+                assert!(s.parse::<Regex<char>>().unwrap().eq(regex), "{}", s);
+            }
+        }
+
+        let regex = Regex::literal("a|(*)\\".chars().collect(), "a|(*)\\").unwrap();
+        assert_eq!(regex.to_parseable_string(), "a\\|\\(\\*\\)\\\\");
+        assert!(regex
+            .to_parseable_string()
+            .parse::<Regex<char>>()
+            .unwrap()
+            .eq(&regex));
+
+        let alphabet: HashSet<char> = ['a', 'b', 'c'].iter().copied().collect();
+        let regex =
+            Regex::parse_with_alphabet(alphabet.clone(), "(a|b|c)?.~(ab){2,}|c{,3}\\e").unwrap();
+        let s = regex.to_parseable_string();
+        assert!(
+            Regex::parse_with_alphabet(alphabet, &s).unwrap().eq(&regex),
+            "{}",
+            s
+        );
+    }
+
+    #[test]
+    fn test_complement() {
+        for (aut, acc, rej) in automaton_list() {
+            let dfa = aut.to_dfa().complement();
+            let nfa = aut.clone().complement();
+            assert!(dfa.eq(&aut.clone().negate()));
+            assert!(nfa.eq(&dfa));
+            assert!(acc.iter().all(|x| !dfa.run(x) && !nfa.run(x)));
+            assert!(rej.iter().all(|x| dfa.run(x) && nfa.run(x)));
+        }
+    }
+
+    #[test]
+    fn test_contains_empty_full() {
+        let alphabet: HashSet<char> = ['a', 'b'].iter().copied().collect();
+        let mut list: Vec<NFA<char>> = automaton_list().into_iter().map(|x| x.0).collect();
+        list.push(NFA::new_empty(alphabet.clone()));
+        list.push(NFA::new_full(alphabet.clone()));
+        list.push(NFA::new_full(['a'].iter().copied().collect()));
+        list.push(NFA::new_length(alphabet, 0));
+
+        for a in &list {
+            for b in &list {
+                let difference = b.clone().difference(a.clone());
+                assert_eq!(a.contains(b), difference.is_empty());
+                assert_eq!(
+                    a.contains_witness(b).err().map(|x| x.len()),
+                    difference.shortest_accepted().map(|x| x.len())
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_to_dfa_labeled() {
+        for (aut, acc, rej) in automaton_list() {
+            let (dfa, labels) = aut.to_dfa_labeled();
+            assert!(dfa.eq(&aut));
+            assert_eq!(labels.len(), dfa.to_csv().lines().count() - 1);
+            assert_eq!(labels.iter().collect::<HashSet<_>>().len(), labels.len());
+
+            let initials: BTreeSet<usize> =
+                aut.epsilon_closure(aut.initials()).into_iter().collect();
+            assert_eq!(labels[0], initials);
+
+            for word in acc.iter().chain(&rej) {
+                let mut runner = dfa.runner();
+                for letter in word {
+                    runner.feed(*letter);
+                }
+                if let Some(state) = runner.state() {
+                    assert_eq!(
+                        labels[state].iter().any(|x| aut.finals().contains(x)),
+                        acc.contains(word)
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_union_concat_ref() {
+        let list = automaton_list();
+        for (aut1, _, _) in &list {
+            for (aut2, _, _) in &list {
+                assert!(aut1.union_ref(aut2).eq(&aut1.clone().unite(aut2.clone())));
+                assert!(aut1
+                    .concat_ref(aut2)
+                    .eq(&aut1.clone().concatenate(aut2.clone())));
+            }
+        }
+    }
+
+    #[test]
+    fn test_words_up_to() {
+        for (aut, acc, rej) in automaton_list() {
+            let dfa = aut.to_dfa();
+            let words = dfa.words_up_to(4);
+            let expected: Vec<Vec<char>> = dfa.words().take_while(|x| x.len() <= 4).collect();
+            assert_eq!(words, expected);
+            assert!(acc
+                .iter()
+                .filter(|x| x.len() <= 4)
+                .all(|x| words.contains(x)));
+            assert!(rej.iter().all(|x| !words.contains(x)));
+        }
+
+        let dfa = "(ab)*".parse::<NFA<char>>().unwrap().to_dfa();
+        assert_eq!(
+            dfa.words_up_to(5),
+            vec![vec![], vec!['a', 'b'], vec!['a', 'b', 'a', 'b']]
+        );
+        assert_eq!(dfa.words_up_to(0), vec![Vec::<char>::new()]);
+        assert!("a+"
+            .parse::<NFA<char>>()
+            .unwrap()
+            .to_dfa()
+            .words_up_to(0)
+            .is_empty());
+    }
+
+    #[test]
+    fn test_is_prefix_free() {
+        let alphabet: HashSet<char> = ['0', '1'].iter().copied().collect();
+        let parse = |s: &str| {
+            Regex::parse_with_alphabet(alphabet.clone(), s)
+                .unwrap()
+                .to_dfa()
+        };
+
+        assert!(!parse("0|01").is_prefix_free());
+        assert!(parse("00|01").is_prefix_free());
+        assert!(parse("0*1").is_prefix_free());
+        assert!(!parse("0*").is_prefix_free());
+        assert!(parse("𝜀").is_prefix_free());
+        assert!(!parse("𝜀|1").is_prefix_free());
+        assert!(DFA::new_empty(&alphabet).is_prefix_free());
+        assert!(parse("0|10").complete().is_prefix_free());
+    }
+
+    #[test]
+    fn test_residual_count() {
+        let alphabet: HashSet<char> = ['a', 'b'].iter().copied().collect();
+        let parse = |s: &str| {
+            Regex::parse_with_alphabet(alphabet.clone(), s)
+                .unwrap()
+                .to_dfa()
+        };
+
+        assert_eq!(parse("(ab)*").residual_count(), 2);
+        assert_eq!(parse("(a|b)*").residual_count(), 1);
+        assert_eq!(parse("a").residual_count(), 2);
+        assert_eq!(DFA::new_empty(&alphabet).residual_count(), 0);
+        for n in 1..5 {
+            assert_eq!(nth_letter_from_end(n).to_dfa().residual_count(), 1 << n);
+        }
+
+        for (aut, _, _) in automaton_list() {
+            let dfa = aut.to_dfa();
+            let count = dfa.residual_count();
+            assert_eq!(count, dfa.clone().minimize().residual_count());
+            assert!(dfa.pumping_length() - count <= 1);
+        }
+    }
+
+    #[test]
+    fn test_new_length_independent_states() {
+        let alphabet: HashSet<char> = ['a', 'b'].iter().copied().collect();
+        let mut aut = NFA::new_length(alphabet.clone(), 1000);
+        let before: Vec<_> = aut.transitions().collect();
+        aut.add_transition(500, 'c', 0);
+
+        let mut after: Vec<_> = aut.transitions().collect();
+        assert_eq!(after.len(), before.len() + 1);
+        after.retain(|x| *x != (500, 'c', 0));
+        assert_eq!(after, before);
+
+        aut.add_epsilon(500, 0);
+        assert_eq!(
+            aut.epsilon_transitions().collect::<Vec<_>>(),
+            vec![(500, 0)]
+        );
+
+        let mut aut = NFA::new_matching(alphabet, &['a'; 1000]).reverse();
+        aut.add_transition(0, 'b', 0);
+        assert_eq!(aut.transitions().filter(|x| x.1 == 'b').count(), 1);
+        aut.add_epsilon(0, 1);
+        assert_eq!(aut.epsilon_transitions().count(), 1);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn test_simplify_empty_range() {
+        for s in &["a{1,3}", "𝜀{1,3}", "(ab)*{1,3}"] {
+            let regex =
+                Regex::parse_with_alphabet(['a', 'b'].iter().copied().collect(), s).unwrap();
+            // the parser rejects empty ranges, so the bounds are swapped in the serialized regex
+            let json = serde_json::to_string(&regex)
+                .unwrap()
+                .replace(",1,3]", ",3,1]");
+            let regex = serde_json::from_str::<Regex<char>>(&json).unwrap();
+            assert!(regex.to_nfa().is_empty());
+            assert!(!regex.is_match(&[]));
+            assert_eq!(regex.clone().simplify().to_string(), "∅");
+            assert!(regex.clone().simplify().eq(&regex));
+        }
+    }
+
+    #[test]
+    fn test_is_match_long_word() {
+        let regex = "(a|ab)*b".parse::<Regex<char>>().unwrap();
+        let mut word: Vec<char> = "ab".repeat(300).chars().collect();
+        word.push('b');
+        assert!(regex.is_match(&word));
+        word.push('a');
+        assert!(!regex.is_match(&word));
+        assert!(!regex.is_match(&['a'; 500]));
+
+        // the derivatives of a repetition end with a concatenation of `R{0}`, which is the empty word
+        let regex = "a{0}b{0}".parse::<Regex<char>>().unwrap();
+        assert_eq!(regex.clone().simplify().to_string(), "𝜀");
+        assert!(regex.is_match(&[]));
+    }
 }